@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use crate::string::levenshtein;
+
+#[derive(Debug, Clone)]
+struct Node {
+    word: String,
+    children: HashMap<usize, usize>,
+}
+
+/// A BK-tree, a metric tree for typo-tolerant dictionary lookups.
+///
+/// Queries only visit the subtrees that can hold a word within the requested
+/// distance, which is far fewer than a linear scan for small distances.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::collections::BkTree;
+///
+/// let mut tree = BkTree::new();
+/// for word in ["build", "bench", "check", "clean", "clippy"] {
+///     tree.insert(word);
+/// }
+///
+/// let answer = tree.query("biuld", 2);
+/// assert_eq!(answer, vec![("build", 2)]);
+///
+/// let answer = tree.query("clen", 1);
+/// assert_eq!(answer, vec![("clean", 1)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BkTree {
+    nodes: Vec<Node>,
+    metric: fn(&str, &str) -> usize,
+}
+
+impl Default for BkTree {
+    fn default() -> Self {
+        BkTree::with_metric(levenshtein)
+    }
+}
+
+impl BkTree {
+    /// Create an empty tree keyed by Levenshtein distance.
+    pub fn new() -> Self {
+        BkTree::default()
+    }
+
+    /// Create an empty tree keyed by another distance. It must be a metric,
+    /// in particular satisfy the triangle inequality, or queries can miss
+    /// words. [`crate::string::levenshtein`] is a metric, while
+    /// [`crate::string::damerau_levenshtein`] is not: it's the optimal string
+    /// alignment distance, where "ca" to "ac" is 1 and "ac" to "abc" is 1 but
+    /// "ca" to "abc" is 3.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::collections::BkTree;
+    /// use gutils::string::levenshtein;
+    ///
+    /// let mut tree = BkTree::with_metric(levenshtein);
+    /// tree.insert("status");
+    ///
+    /// let answer = tree.query("stauts", 2);
+    /// assert_eq!(answer, vec![("status", 2)]);
+    /// ```
+    pub fn with_metric(metric: fn(&str, &str) -> usize) -> Self {
+        BkTree { nodes: vec![], metric }
+    }
+
+    /// Return the number of words in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return whether true or false if the tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Insert a word. Return whether true or false if it was not present yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::collections::BkTree;
+    ///
+    /// let mut tree = BkTree::new();
+    /// assert_eq!(tree.insert("kek"), true);
+    /// assert_eq!(tree.insert("kek"), false);
+    /// assert_eq!(tree.len(), 1);
+    /// ```
+    pub fn insert(&mut self, word: &str) -> bool {
+        let new = Node { word: word.to_string(), children: HashMap::new() };
+
+        if self.nodes.is_empty() {
+            self.nodes.push(new);
+            return true;
+        }
+
+        let mut current = 0;
+
+        loop {
+            let distance = (self.metric)(&self.nodes[current].word, word);
+
+            if distance == 0 {
+                return false;
+            }
+
+            match self.nodes[current].children.get(&distance) {
+                Some(&child) => current = child,
+                None => {
+                    let idx = self.nodes.len();
+                    self.nodes.push(new);
+                    self.nodes[current].children.insert(distance, idx);
+                    return true;
+                }
+            }
+        }
+    }
+
+    /// Return whether true or false if the word is in the tree.
+    pub fn contains(&self, word: &str) -> bool {
+        !self.query(word, 0).is_empty()
+    }
+
+    /// Return every word within `max_distance` of the given one, with its
+    /// distance, closest first and alphabetically among equal distances.
+    pub fn query(&self, word: &str, max_distance: usize) -> Vec<(&str, usize)> {
+        let mut result = vec![];
+        let mut stack = if self.nodes.is_empty() { vec![] } else { vec![0] };
+
+        while let Some(idx) = stack.pop() {
+            let node = &self.nodes[idx];
+            let distance = (self.metric)(&node.word, word);
+
+            if distance <= max_distance {
+                result.push((node.word.as_str(), distance));
+            }
+
+            let range = distance.saturating_sub(max_distance)..=distance.saturating_add(max_distance);
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|(d, _)| range.contains(d))
+                    .map(|(_, &child)| child),
+            );
+        }
+
+        result.sort_unstable_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)));
+        result
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() {
+        let words = ["book", "books", "cake", "boo", "boon", "cook", "cape", "cart"];
+        let mut tree = BkTree::new();
+        words.iter().for_each(|w| {
+            tree.insert(w);
+        });
+
+        let answer = tree.query("bo", 2);
+        assert_eq!(answer, vec![("boo", 1), ("book", 2), ("boon", 2)]);
+
+        let mut expected: Vec<(&str, usize)> = words
+            .iter()
+            .map(|&w| (w, levenshtein(w, "caqe")))
+            .filter(|&(_, d)| d <= 2)
+            .collect();
+        expected.sort_unstable_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)));
+        assert_eq!(tree.query("caqe", 2), expected);
+    }
+
+    #[test]
+    fn test_contains() {
+        let mut tree = BkTree::new();
+        assert_eq!(tree.is_empty(), true);
+        assert_eq!(tree.contains("kek"), false);
+
+        tree.insert("кек");
+        tree.insert("лол");
+        assert_eq!(tree.contains("кек"), true);
+        assert_eq!(tree.contains("кеки"), false);
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn test_query_matches_scan() {
+        // Words over a small alphabet, so that many of them are close.
+        let mut seed: u32 = 7;
+        let mut random = move |n: u32| {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            (seed >> 16) % n
+        };
+        let words: Vec<String> = (0..300)
+            .map(|_| (0..random(7)).map(|_| (b'a' + random(3) as u8) as char).collect())
+            .collect();
+
+        let mut tree = BkTree::new();
+        words.iter().for_each(|w| {
+            tree.insert(w);
+        });
+
+        for query in ["", "a", "abc", "cab", "bbbb", "acbac", "cccccc", "abcabcab"] {
+            for max_distance in 0..4 {
+                let mut expected: Vec<(&str, usize)> = tree
+                    .nodes
+                    .iter()
+                    .map(|node| (node.word.as_str(), levenshtein(&node.word, query)))
+                    .filter(|&(_, d)| d <= max_distance)
+                    .collect();
+                expected.sort_unstable_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)));
+
+                assert_eq!(tree.query(query, max_distance), expected);
+            }
+        }
+    }
+}
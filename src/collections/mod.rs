@@ -1,5 +1,14 @@
 use std::{collections::{HashMap, HashSet}, hash::Hash};
 
+mod bk_tree;
+mod trie;
+mod window;
+
+pub use bk_tree::BkTree;
+pub use trie::{RadixTree, Trie};
+pub use window::{longest_window_k_distinct, longest_window_k_repeats, shortest_window_containing};
+pub(crate) use window::FrequencyTable;
+
 /// Return whether true or false if it's a duplicate
 ///
 /// # Examples
@@ -68,19 +77,7 @@ pub fn find_duplicate<T: Eq + Hash + Copy>(list: Vec<T>) -> Option<T> {
 /// assert_eq!(answer, 3);
 /// ```
 pub fn lenght_of_longest_non_repeatable_substring(s: String) -> i32 {
-    let mut hm = HashMap::new();
-    let mut len = 0;
-    let mut start = -1;
-
-    for (end, ch) in s.chars().enumerate() {
-        if let Some(i) = hm.insert(ch, end as i32) {
-            start = start.max(i);
-        }
-
-        len = len.max((end as i32) - start);
-    }
-
-    len as i32
+    longest_window_k_repeats(s.chars(), 1).len() as i32
 }
 
 /// Return two indexes of target' sum
@@ -140,6 +137,7 @@ pub fn find_majority_element(nums: Vec<i32>) -> i32 {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
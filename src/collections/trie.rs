@@ -0,0 +1,593 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+
+/// Shared view of trie and radix tree nodes used for ordered traversal.
+trait PrefixNode<V> {
+    fn value(&self) -> Option<&V>;
+
+    /// Outgoing edges with their labels, in increasing label order.
+    fn edges(&self) -> Vec<(&[u8], &Self)>;
+}
+
+/// Iterate over the entries below `start` in sorted key order.
+fn walk<'a, V: 'a, N: PrefixNode<V>>(start: Option<(Vec<u8>, &'a N)>) -> impl Iterator<Item = (Vec<u8>, &'a V)> {
+    let mut stack: Vec<(Vec<u8>, &'a N)> = start.into_iter().collect();
+
+    std::iter::from_fn(move || {
+        while let Some((key, node)) = stack.pop() {
+            for (label, child) in node.edges().into_iter().rev() {
+                let mut child_key = key.clone();
+                child_key.extend_from_slice(label);
+                stack.push((child_key, child));
+            }
+
+            if let Some(value) = node.value() {
+                return Some((key, value));
+            }
+        }
+
+        None
+    })
+}
+
+/// Return the `k` entries with the greatest values, ties broken by key order.
+fn top_k<'a, V: Ord + 'a>(entries: impl Iterator<Item = (Vec<u8>, &'a V)>, k: usize) -> Vec<(Vec<u8>, &'a V)> {
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+
+    for (key, value) in entries {
+        heap.push(Reverse((value, Reverse(key))));
+
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((value, Reverse(key)))| (key, value))
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+struct TrieNode<V> {
+    value: Option<V>,
+    children: BTreeMap<u8, TrieNode<V>>,
+}
+
+impl<V> TrieNode<V> {
+    fn new() -> Self {
+        TrieNode { value: None, children: BTreeMap::new() }
+    }
+
+    fn remove(&mut self, key: &[u8]) -> Option<V> {
+        let Some((first, rest)) = key.split_first() else {
+            return self.value.take();
+        };
+
+        let child = self.children.get_mut(first)?;
+        let removed = child.remove(rest);
+
+        if child.value.is_none() && child.children.is_empty() {
+            self.children.remove(first);
+        }
+
+        removed
+    }
+}
+
+impl<V> PrefixNode<V> for TrieNode<V> {
+    fn value(&self) -> Option<&V> {
+        self.value.as_ref()
+    }
+
+    fn edges(&self) -> Vec<(&[u8], &Self)> {
+        self.children.iter().map(|(b, child)| (std::slice::from_ref(b), child)).collect()
+    }
+}
+
+/// A trie with one node per key byte. Keys can be `&str`, `String`, `&[u8]`
+/// or anything else that is `AsRef<[u8]>`, and are returned as bytes.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::collections::Trie;
+///
+/// let mut trie = Trie::new();
+/// trie.insert("car", 1);
+/// trie.insert("cart", 2);
+/// trie.insert("care", 3);
+/// trie.insert("dog", 4);
+///
+/// assert_eq!(trie.get("cart"), Some(&2));
+/// assert_eq!(trie.get("ca"), None);
+/// assert_eq!(trie.longest_prefix("cartoon"), Some((4, &2)));
+///
+/// let answer: Vec<Vec<u8>> = trie.iter_prefix("car").map(|(key, _)| key).collect();
+/// assert_eq!(answer, vec![b"car".to_vec(), b"care".to_vec(), b"cart".to_vec()]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Trie<V> {
+    root: TrieNode<V>,
+    len: usize,
+}
+
+impl<V> Default for Trie<V> {
+    fn default() -> Self {
+        Trie { root: TrieNode::new(), len: 0 }
+    }
+}
+
+impl<V> Trie<V> {
+    pub fn new() -> Self {
+        Trie::default()
+    }
+
+    /// Return the number of keys.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return whether true or false if the trie is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn node(&self, key: &[u8]) -> Option<&TrieNode<V>> {
+        key.iter().try_fold(&self.root, |node, b| node.children.get(b))
+    }
+
+    /// Insert a key and return the value it replaced, if any.
+    pub fn insert<K: AsRef<[u8]>>(&mut self, key: K, value: V) -> Option<V> {
+        let node = key
+            .as_ref()
+            .iter()
+            .fold(&mut self.root, |node, &b| node.children.entry(b).or_insert_with(TrieNode::new));
+        let old = node.value.replace(value);
+
+        if old.is_none() {
+            self.len += 1;
+        }
+
+        old
+    }
+
+    /// Return the value of the key.
+    pub fn get<K: AsRef<[u8]>>(&self, key: K) -> Option<&V> {
+        self.node(key.as_ref()).and_then(|node| node.value.as_ref())
+    }
+
+    /// Return whether true or false if the key is present.
+    pub fn contains_key<K: AsRef<[u8]>>(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Remove the key and return its value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::collections::Trie;
+    ///
+    /// let mut trie = Trie::new();
+    /// trie.insert("kek", 1);
+    ///
+    /// assert_eq!(trie.remove("ke"), None);
+    /// assert_eq!(trie.remove("kek"), Some(1));
+    /// assert_eq!(trie.is_empty(), true);
+    /// ```
+    pub fn remove<K: AsRef<[u8]>>(&mut self, key: K) -> Option<V> {
+        let removed = self.root.remove(key.as_ref());
+
+        if removed.is_some() {
+            self.len -= 1;
+        }
+
+        removed
+    }
+
+    /// Return the byte length and value of the longest stored key that is a
+    /// prefix of the given key.
+    pub fn longest_prefix<K: AsRef<[u8]>>(&self, key: K) -> Option<(usize, &V)> {
+        let mut node = &self.root;
+        let mut best = node.value.as_ref().map(|v| (0, v));
+
+        for (i, b) in key.as_ref().iter().enumerate() {
+            match node.children.get(b) {
+                Some(child) => node = child,
+                None => break,
+            }
+
+            if let Some(value) = &node.value {
+                best = Some((i + 1, value));
+            }
+        }
+
+        best
+    }
+
+    /// Return all entries in sorted key order.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec<u8>, &V)> {
+        self.iter_prefix([])
+    }
+
+    /// Return the entries whose key starts with the prefix, in sorted key order.
+    pub fn iter_prefix<K: AsRef<[u8]>>(&self, prefix: K) -> impl Iterator<Item = (Vec<u8>, &V)> {
+        let prefix = prefix.as_ref();
+
+        walk(self.node(prefix).map(|node| (prefix.to_vec(), node)))
+    }
+
+    /// Return the `k` entries starting with the prefix that have the greatest
+    /// values, greatest first and in key order among equal values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::collections::Trie;
+    ///
+    /// let mut trie = Trie::new();
+    /// trie.insert("git commit", 50);
+    /// trie.insert("git checkout", 30);
+    /// trie.insert("git cherry-pick", 5);
+    /// trie.insert("grep", 100);
+    ///
+    /// let answer = trie.top_k("git c", 2);
+    /// assert_eq!(answer, vec![(b"git commit".to_vec(), &50), (b"git checkout".to_vec(), &30)]);
+    /// ```
+    pub fn top_k<K: AsRef<[u8]>>(&self, prefix: K, k: usize) -> Vec<(Vec<u8>, &V)>
+    where
+        V: Ord,
+    {
+        top_k(self.iter_prefix(prefix), k)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RadixNode<V> {
+    value: Option<V>,
+    /// Edges with non-empty labels, sorted and unique by their first byte.
+    children: Vec<(Vec<u8>, RadixNode<V>)>,
+}
+
+fn common_prefix(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+impl<V> RadixNode<V> {
+    fn new(value: Option<V>) -> Self {
+        RadixNode { value, children: vec![] }
+    }
+
+    fn edge(&self, b: u8) -> Result<usize, usize> {
+        self.children.binary_search_by_key(&b, |(label, _)| label[0])
+    }
+
+    fn insert(&mut self, key: &[u8], value: V) -> Option<V> {
+        if key.is_empty() {
+            return self.value.replace(value);
+        }
+
+        let i = match self.edge(key[0]) {
+            Ok(i) => i,
+            Err(i) => {
+                self.children.insert(i, (key.to_vec(), RadixNode::new(Some(value))));
+                return None;
+            }
+        };
+
+        let (label, child) = &mut self.children[i];
+        let common = common_prefix(label, key);
+
+        if common < label.len() {
+            let suffix = label.split_off(common);
+            let old = std::mem::replace(child, RadixNode::new(None));
+            child.children.push((suffix, old));
+        }
+
+        child.insert(&key[common..], value)
+    }
+
+    fn remove(&mut self, key: &[u8]) -> Option<V> {
+        if key.is_empty() {
+            return self.value.take();
+        }
+
+        let i = self.edge(key[0]).ok()?;
+        let (label, child) = &mut self.children[i];
+
+        if !key.starts_with(label) {
+            return None;
+        }
+
+        let removed = child.remove(&key[label.len()..])?;
+
+        if child.value.is_none() {
+            match child.children.len() {
+                0 => {
+                    self.children.remove(i);
+                }
+                1 => {
+                    let (rest, grandchild) = child.children.pop().unwrap();
+                    label.extend(rest);
+                    *child = grandchild;
+                }
+                _ => {}
+            }
+        }
+
+        Some(removed)
+    }
+}
+
+impl<V> PrefixNode<V> for RadixNode<V> {
+    fn value(&self) -> Option<&V> {
+        self.value.as_ref()
+    }
+
+    fn edges(&self) -> Vec<(&[u8], &Self)> {
+        self.children.iter().map(|(label, child)| (label.as_slice(), child)).collect()
+    }
+}
+
+/// A radix tree, i.e. a trie whose single-child chains are merged into one
+/// edge, which keeps it small for long keys with shared prefixes. Keys can be
+/// `&str`, `String`, `&[u8]` or anything else that is `AsRef<[u8]>`, and are
+/// returned as bytes.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::collections::RadixTree;
+///
+/// let mut tree = RadixTree::new();
+/// tree.insert("/api/users", "users");
+/// tree.insert("/api/users/active", "active");
+/// tree.insert("/static", "files");
+///
+/// assert_eq!(tree.get("/api/users"), Some(&"users"));
+/// assert_eq!(tree.longest_prefix("/api/users/42"), Some((10, &"users")));
+///
+/// let answer: Vec<Vec<u8>> = tree.iter_prefix("/api").map(|(key, _)| key).collect();
+/// assert_eq!(answer, vec![b"/api/users".to_vec(), b"/api/users/active".to_vec()]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RadixTree<V> {
+    root: RadixNode<V>,
+    len: usize,
+}
+
+impl<V> Default for RadixTree<V> {
+    fn default() -> Self {
+        RadixTree { root: RadixNode::new(None), len: 0 }
+    }
+}
+
+impl<V> RadixTree<V> {
+    pub fn new() -> Self {
+        RadixTree::default()
+    }
+
+    /// Return the number of keys.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return whether true or false if the tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Insert a key and return the value it replaced, if any.
+    pub fn insert<K: AsRef<[u8]>>(&mut self, key: K, value: V) -> Option<V> {
+        let old = self.root.insert(key.as_ref(), value);
+
+        if old.is_none() {
+            self.len += 1;
+        }
+
+        old
+    }
+
+    /// Return the value of the key.
+    pub fn get<K: AsRef<[u8]>>(&self, key: K) -> Option<&V> {
+        let mut node = &self.root;
+        let mut key = key.as_ref();
+
+        while !key.is_empty() {
+            let (label, child) = &node.children[node.edge(key[0]).ok()?];
+
+            key = key.strip_prefix(label.as_slice())?;
+            node = child;
+        }
+
+        node.value.as_ref()
+    }
+
+    /// Return whether true or false if the key is present.
+    pub fn contains_key<K: AsRef<[u8]>>(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Remove the key and return its value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::collections::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new();
+    /// tree.insert("team", 1);
+    /// tree.insert("test", 2);
+    ///
+    /// assert_eq!(tree.remove("te"), None);
+    /// assert_eq!(tree.remove("team"), Some(1));
+    /// assert_eq!(tree.get("test"), Some(&2));
+    /// assert_eq!(tree.len(), 1);
+    /// ```
+    pub fn remove<K: AsRef<[u8]>>(&mut self, key: K) -> Option<V> {
+        let removed = self.root.remove(key.as_ref());
+
+        if removed.is_some() {
+            self.len -= 1;
+        }
+
+        removed
+    }
+
+    /// Return the byte length and value of the longest stored key that is a
+    /// prefix of the given key.
+    pub fn longest_prefix<K: AsRef<[u8]>>(&self, key: K) -> Option<(usize, &V)> {
+        let key = key.as_ref();
+        let mut node = &self.root;
+        let mut depth = 0;
+        let mut best = node.value.as_ref().map(|v| (0, v));
+
+        while let Some(&b) = key.get(depth) {
+            let Ok(i) = node.edge(b) else {
+                break;
+            };
+            let (label, child) = &node.children[i];
+
+            if !key[depth..].starts_with(label) {
+                break;
+            }
+
+            depth += label.len();
+            node = child;
+
+            if let Some(value) = &node.value {
+                best = Some((depth, value));
+            }
+        }
+
+        best
+    }
+
+    /// Return all entries in sorted key order.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec<u8>, &V)> {
+        self.iter_prefix([])
+    }
+
+    /// Return the entries whose key starts with the prefix, in sorted key order.
+    pub fn iter_prefix<K: AsRef<[u8]>>(&self, prefix: K) -> impl Iterator<Item = (Vec<u8>, &V)> {
+        let mut rest = prefix.as_ref();
+        let mut node = &self.root;
+        let mut key = vec![];
+
+        // The prefix may end in the middle of an edge, then the whole edge is taken.
+        let start = loop {
+            if rest.is_empty() {
+                break Some((key, node));
+            }
+
+            let Ok(i) = node.edge(rest[0]) else {
+                break None;
+            };
+            let (label, child) = &node.children[i];
+            let common = common_prefix(label, rest);
+
+            if common < label.len() && common < rest.len() {
+                break None;
+            }
+
+            key.extend_from_slice(label);
+            rest = &rest[common..];
+            node = child;
+        };
+
+        walk(start)
+    }
+
+    /// Return the `k` entries starting with the prefix that have the greatest
+    /// values, greatest first and in key order among equal values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::collections::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new();
+    /// tree.insert("привет", 3);
+    /// tree.insert("привал", 7);
+    /// tree.insert("пока", 9);
+    ///
+    /// let answer = tree.top_k("при", 1);
+    /// assert_eq!(answer, vec![("привал".as_bytes().to_vec(), &7)]);
+    /// ```
+    pub fn top_k<K: AsRef<[u8]>>(&self, prefix: K, k: usize) -> Vec<(Vec<u8>, &V)>
+    where
+        V: Ord,
+    {
+        top_k(self.iter_prefix(prefix), k)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 10] = ["", "a", "ab", "abc", "abd", "b", "ba", "banana", "band", "bandana"];
+
+    #[test]
+    fn test_trie() {
+        let mut trie = Trie::new();
+        for (i, word) in WORDS.iter().enumerate().rev() {
+            assert_eq!(trie.insert(word, i), None);
+        }
+        assert_eq!(trie.len(), WORDS.len());
+
+        let answer: Vec<Vec<u8>> = trie.iter().map(|(key, _)| key).collect();
+        let expected: Vec<Vec<u8>> = WORDS.iter().map(|w| w.as_bytes().to_vec()).collect();
+        assert_eq!(answer, expected);
+
+        assert_eq!(trie.longest_prefix("abz"), Some((2, &2)));
+        assert_eq!(trie.longest_prefix("zzz"), Some((0, &0)));
+        assert_eq!(trie.remove("ab"), Some(2));
+        assert_eq!(trie.longest_prefix("abz"), Some((1, &1)));
+        assert_eq!(trie.contains_key("abc"), true);
+
+        let answer = trie.top_k("ba", 2);
+        assert_eq!(answer, vec![(b"bandana".to_vec(), &9), (b"band".to_vec(), &8)]);
+    }
+
+    #[test]
+    fn test_radix_tree() {
+        let mut tree = RadixTree::new();
+        for (i, word) in WORDS.iter().enumerate().rev() {
+            assert_eq!(tree.insert(word, i), None);
+        }
+        assert_eq!(tree.insert("band", 80), Some(8));
+        assert_eq!(tree.len(), WORDS.len());
+
+        let answer: Vec<Vec<u8>> = tree.iter().map(|(key, _)| key).collect();
+        let expected: Vec<Vec<u8>> = WORDS.iter().map(|w| w.as_bytes().to_vec()).collect();
+        assert_eq!(answer, expected);
+
+        let answer: Vec<Vec<u8>> = tree.iter_prefix("bana").map(|(key, _)| key).collect();
+        assert_eq!(answer, vec![b"banana".to_vec()]);
+
+        for word in WORDS {
+            assert_eq!(tree.remove(word).is_some(), true);
+            assert_eq!(tree.get(word), None);
+        }
+        assert_eq!(tree.is_empty(), true);
+        assert_eq!(tree.root.children.len(), 0);
+    }
+
+    #[test]
+    fn test_radix_tree_compression() {
+        let mut tree = RadixTree::new();
+        tree.insert("romane", 1);
+        tree.insert("romanus", 2);
+        tree.insert("romulus", 3);
+        tree.remove("romanus");
+
+        assert_eq!(tree.root.children.len(), 1);
+        assert_eq!(tree.root.children[0].0, b"rom".to_vec());
+        assert_eq!(tree.root.children[0].1.children[0].0, b"ane".to_vec());
+        assert_eq!(tree.get("romane"), Some(&1));
+        assert_eq!(tree.get("roman"), None);
+    }
+}
@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Range;
+
+/// Frequency table of the required items against a sliding window.
+///
+/// Every required item starts with a positive balance of how many times it's
+/// required; items entering the window decrease it and leaving increase it.
+/// Items that are not required are ignored.
+#[derive(Debug, Clone)]
+pub(crate) struct FrequencyTable<'a, T> {
+    balance: HashMap<&'a T, isize>,
+    missing: usize,
+}
+
+impl<'a, T: Hash + Eq> FrequencyTable<'a, T> {
+    pub(crate) fn new(required: &'a [T]) -> Self {
+        let mut balance = HashMap::new();
+
+        for item in required {
+            *balance.entry(item).or_insert(0) += 1;
+        }
+
+        FrequencyTable { balance, missing: required.len() }
+    }
+
+    /// Account for an item entering the window.
+    pub(crate) fn push(&mut self, item: &T) {
+        if let Some(count) = self.balance.get_mut(item) {
+            if *count > 0 {
+                self.missing -= 1;
+            }
+            *count -= 1;
+        }
+    }
+
+    /// Account for an item leaving the window.
+    pub(crate) fn pop(&mut self, item: &T) {
+        if let Some(count) = self.balance.get_mut(item) {
+            *count += 1;
+
+            if *count > 0 {
+                self.missing += 1;
+            }
+        }
+    }
+
+    /// Return whether true or false if the item can leave the window without
+    /// uncovering a required one.
+    pub(crate) fn is_spare(&self, item: &T) -> bool {
+        self.balance.get(item).is_none_or(|&count| count < 0)
+    }
+
+    /// Return whether true or false if the window holds every required item.
+    pub(crate) fn is_covered(&self) -> bool {
+        self.missing == 0
+    }
+}
+
+/// Return the leftmost longest window in which `fits` holds, where `fits`
+/// only turns false when the window grows and true again when it shrinks.
+///
+/// `counts` holds how many times each item occurs in the current window.
+fn longest_window<T, F>(items: &[T], mut fits: F) -> Range<usize>
+where
+    T: Hash + Eq,
+    F: FnMut(&HashMap<&T, usize>, &T) -> bool,
+{
+    let mut counts: HashMap<&T, usize> = HashMap::new();
+    let mut best = 0..0;
+    let mut start = 0;
+
+    for (end, item) in items.iter().enumerate() {
+        *counts.entry(item).or_insert(0) += 1;
+
+        while start <= end && !fits(&counts, item) {
+            let count = counts.get_mut(&items[start]).unwrap();
+            *count -= 1;
+
+            if *count == 0 {
+                counts.remove(&items[start]);
+            }
+
+            start += 1;
+        }
+
+        if end + 1 - start > best.len() {
+            best = start..end + 1;
+        }
+    }
+
+    best
+}
+
+/// Return the bounds of the leftmost longest window with at most `k` distinct
+/// items. Works on any iterator, e.g. `s.chars()` gives char indices.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::collections::longest_window_k_distinct;
+///
+/// let answer = longest_window_k_distinct("eceba".chars(), 2);
+/// assert_eq!(answer, 0..3);
+///
+/// let answer = longest_window_k_distinct([1, 2, 1, 2, 3, 3, 3, 3], 2);
+/// assert_eq!(answer.len(), 5);
+/// ```
+pub fn longest_window_k_distinct<I>(items: I, k: usize) -> Range<usize>
+where
+    I: IntoIterator,
+    I::Item: Hash + Eq,
+{
+    let items: Vec<I::Item> = items.into_iter().collect();
+
+    longest_window(&items, |counts, _| counts.len() <= k)
+}
+
+/// Return the bounds of the leftmost longest window in which no item occurs
+/// more than `k` times. With `k = 1` it's the longest window without repeats.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::collections::longest_window_k_repeats;
+///
+/// let answer = longest_window_k_repeats("abcabcbb".chars(), 1);
+/// assert_eq!(answer, 0..3);
+///
+/// let answer = longest_window_k_repeats("aaabbbaab".chars(), 2);
+/// assert_eq!(answer, 1..5);
+/// ```
+pub fn longest_window_k_repeats<I>(items: I, k: usize) -> Range<usize>
+where
+    I: IntoIterator,
+    I::Item: Hash + Eq,
+{
+    let items: Vec<I::Item> = items.into_iter().collect();
+
+    // Only the newest item can push its count over the limit.
+    longest_window(&items, |counts, last| counts[last] <= k)
+}
+
+/// Return the bounds of the leftmost shortest window that contains every
+/// required item, as many times as it's repeated in `required`.
+/// Return `None` if there is no such window.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::collections::shortest_window_containing;
+///
+/// let answer = shortest_window_containing("ADOBECODEBANC".chars(), "ABC".chars());
+/// assert_eq!(answer, Some(9..13));
+///
+/// let answer = shortest_window_containing([1, 2, 2, 3, 1, 2], [2, 2, 1]);
+/// assert_eq!(answer, Some(0..3));
+///
+/// let answer = shortest_window_containing("ab".chars(), "aa".chars());
+/// assert_eq!(answer, None);
+/// ```
+pub fn shortest_window_containing<I, R>(items: I, required: R) -> Option<Range<usize>>
+where
+    I: IntoIterator,
+    I::Item: Hash + Eq,
+    R: IntoIterator<Item = I::Item>,
+{
+    let items: Vec<I::Item> = items.into_iter().collect();
+    let required: Vec<I::Item> = required.into_iter().collect();
+
+    if required.is_empty() {
+        return Some(0..0);
+    }
+
+    let mut table = FrequencyTable::new(&required);
+    let mut best: Option<Range<usize>> = None;
+    let mut start = 0;
+
+    for (end, item) in items.iter().enumerate() {
+        table.push(item);
+
+        if !table.is_covered() {
+            continue;
+        }
+
+        while table.is_spare(&items[start]) {
+            table.pop(&items[start]);
+            start += 1;
+        }
+
+        if best.as_ref().is_none_or(|b| end + 1 - start < b.len()) {
+            best = Some(start..end + 1);
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_window_k_distinct() {
+        assert_eq!(longest_window_k_distinct("".chars(), 2), 0..0);
+        assert_eq!(longest_window_k_distinct("abc".chars(), 0), 0..0);
+        assert_eq!(longest_window_k_distinct("aabbcc".chars(), 1), 0..2);
+        assert_eq!(longest_window_k_distinct("мама мыла".chars(), 2), 0..4);
+        assert_eq!(longest_window_k_distinct(["x", "y", "x", "z"], 5), 0..4);
+    }
+
+    #[test]
+    fn test_longest_window_k_repeats() {
+        assert_eq!(longest_window_k_repeats("abcdeffeacb".chars(), 1), 0..6);
+        assert_eq!(longest_window_k_repeats("bbbbb".chars(), 1), 0..1);
+        assert_eq!(longest_window_k_repeats("bbbbb".chars(), 0), 0..0);
+        assert_eq!(longest_window_k_repeats(vec![1, 1, 2, 1, 2, 2], 2), 1..5);
+    }
+
+    #[test]
+    fn test_shortest_window_containing() {
+        assert_eq!(shortest_window_containing("abc".chars(), "".chars()), Some(0..0));
+        assert_eq!(shortest_window_containing("".chars(), "a".chars()), None);
+        assert_eq!(shortest_window_containing("aXbXaab".chars(), "aab".chars()), Some(4..7));
+        assert_eq!(shortest_window_containing("привет мир".chars(), "ир".chars()), Some(1..3));
+    }
+}
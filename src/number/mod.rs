@@ -120,7 +120,42 @@ pub fn find_single_number(nums: Vec<i32>) -> i32 {
     mask
 }
 
+/// Return the `n`-th Catalan number, or `None` if it doesn't fit in `u128`.
+/// Every step divides before it multiplies, so nothing overflows while the
+/// result fits.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::number::catalan;
+///
+/// let answer = catalan(5);
+/// assert_eq!(answer, Some(42));
+///
+/// let answer = catalan(1000);
+/// assert_eq!(answer, None);
+/// ```
+pub fn catalan(n: u32) -> Option<u128> {
+    let gcd = |mut a: u128, mut b: u128| {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+
+        a
+    };
+    let mut result: u128 = 1;
+
+    // C(i + 1) = C(i) * 2(2i + 1) / (i + 2), and the division is exact.
+    for i in 0..n as u128 {
+        let g = gcd(result, i + 2);
+        result = (result / g).checked_mul(2 * (2 * i + 1) / ((i + 2) / g))?;
+    }
+
+    Some(result)
+}
+
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -222,4 +257,19 @@ mod tests {
         let answer = find_single_number(vec![1,1,2,2,3,3]);
         assert_eq!(answer, 0);
     }
+
+    #[test]
+    fn test_catalan() {
+        let answer: Vec<u128> = (0..10).map(|n| catalan(n).unwrap()).collect();
+        assert_eq!(answer, vec![1, 1, 2, 5, 14, 42, 132, 429, 1430, 4862]);
+
+        let answer = catalan(30);
+        assert_eq!(answer, Some(3814986502092304));
+
+        let answer = catalan(69);
+        assert_eq!(answer, Some(337485502510215975556783793455058624700));
+
+        let answer = catalan(70);
+        assert_eq!(answer, None);
+    }
 }
@@ -0,0 +1,352 @@
+/// Which matches an [`AhoCorasick`] automaton reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    /// Every occurrence of every pattern, including overlapping ones.
+    All,
+    /// Non-overlapping matches, preferring the leftmost start and then the
+    /// longest pattern at that start.
+    LeftmostLongest,
+}
+
+/// A pattern found in the text, spanning the byte range `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatternMatch {
+    /// Index of the pattern in the list given to [`AhoCorasick::new`].
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    children: Vec<(u8, usize)>,
+    fail: usize,
+    depth: usize,
+    output: Option<usize>,
+    /// The closest node on the failure chain that ends a pattern.
+    dict: Option<usize>,
+}
+
+impl Node {
+    fn child(&self, b: u8) -> Option<usize> {
+        self.children
+            .binary_search_by_key(&b, |&(c, _)| c)
+            .ok()
+            .map(|i| self.children[i].1)
+    }
+}
+
+/// An Aho-Corasick automaton that finds many patterns in one pass over the text.
+///
+/// Matching is byte-oriented, so `&str` and `&[u8]` are both accepted and the
+/// spans are byte offsets. Empty patterns never match. If the same pattern is
+/// given twice, matches report the lower index.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{AhoCorasick, MatchKind, PatternMatch};
+///
+/// let ac = AhoCorasick::new(&["he", "she", "hers"], MatchKind::All, false);
+/// let answer = ac.find_all("ushers");
+/// assert_eq!(answer, vec![
+///     PatternMatch { pattern: 1, start: 1, end: 4 },
+///     PatternMatch { pattern: 0, start: 2, end: 4 },
+///     PatternMatch { pattern: 2, start: 2, end: 6 },
+/// ]);
+///
+/// let ac = AhoCorasick::new(&["he", "she", "hers"], MatchKind::LeftmostLongest, false);
+/// let answer = ac.find_all("ushers");
+/// assert_eq!(answer, vec![PatternMatch { pattern: 1, start: 1, end: 4 }]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    nodes: Vec<Node>,
+    lens: Vec<usize>,
+    kind: MatchKind,
+    ascii_case_insensitive: bool,
+}
+
+impl AhoCorasick {
+    /// Build the automaton. With `ascii_case_insensitive` set, ASCII letters
+    /// match regardless of case, while other bytes must match exactly.
+    pub fn new<P: AsRef<[u8]>>(patterns: &[P], kind: MatchKind, ascii_case_insensitive: bool) -> Self {
+        let mut nodes = vec![Node::default()];
+        let lens = patterns.iter().map(|p| p.as_ref().len()).collect();
+
+        for (id, pattern) in patterns.iter().enumerate() {
+            let pattern = pattern.as_ref();
+
+            if pattern.is_empty() {
+                continue;
+            }
+
+            let mut current = 0;
+
+            for &b in pattern {
+                let b = if ascii_case_insensitive { b.to_ascii_lowercase() } else { b };
+
+                current = match nodes[current].children.binary_search_by_key(&b, |&(c, _)| c) {
+                    Ok(i) => nodes[current].children[i].1,
+                    Err(i) => {
+                        let depth = nodes[current].depth + 1;
+                        nodes.push(Node { depth, ..Node::default() });
+                        let next = nodes.len() - 1;
+                        nodes[current].children.insert(i, (b, next));
+                        next
+                    }
+                };
+            }
+
+            nodes[current].output.get_or_insert(id);
+        }
+
+        let mut queue = std::collections::VecDeque::from([0]);
+
+        while let Some(u) = queue.pop_front() {
+            for i in 0..nodes[u].children.len() {
+                let (b, v) = nodes[u].children[i];
+                let fail = if u == 0 {
+                    0
+                } else {
+                    let mut f = nodes[u].fail;
+
+                    loop {
+                        if let Some(next) = nodes[f].child(b) {
+                            break next;
+                        }
+                        if f == 0 {
+                            break 0;
+                        }
+                        f = nodes[f].fail;
+                    }
+                };
+
+                nodes[v].fail = fail;
+                nodes[v].dict = if nodes[fail].output.is_some() { Some(fail) } else { nodes[fail].dict };
+                queue.push_back(v);
+            }
+        }
+
+        AhoCorasick { nodes, lens, kind, ascii_case_insensitive }
+    }
+
+    /// Return the number of patterns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::{AhoCorasick, MatchKind};
+    ///
+    /// let ac = AhoCorasick::new(&["kek", "lol"], MatchKind::All, false);
+    /// assert_eq!(ac.patterns_len(), 2);
+    /// ```
+    pub fn patterns_len(&self) -> usize {
+        self.lens.len()
+    }
+
+    /// Return all matches in the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::{AhoCorasick, MatchKind, PatternMatch};
+    ///
+    /// let ac = AhoCorasick::new(&["error", "warn"], MatchKind::LeftmostLongest, true);
+    /// let answer = ac.find_all("WARN: Error");
+    /// assert_eq!(answer, vec![
+    ///     PatternMatch { pattern: 1, start: 0, end: 4 },
+    ///     PatternMatch { pattern: 0, start: 6, end: 11 },
+    /// ]);
+    /// ```
+    pub fn find_all<T: AsRef<[u8]>>(&self, text: T) -> Vec<PatternMatch> {
+        let mut stream = self.stream();
+        let mut result = stream.feed(text);
+
+        result.extend(stream.finish());
+        result
+    }
+
+    /// Return whether true or false if any pattern occurs in the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::{AhoCorasick, MatchKind};
+    ///
+    /// let ac = AhoCorasick::new(&["kek", "lol"], MatchKind::All, false);
+    /// assert_eq!(ac.is_match("ololo"), true);
+    /// assert_eq!(ac.is_match("pip"), false);
+    /// ```
+    pub fn is_match<T: AsRef<[u8]>>(&self, text: T) -> bool {
+        let mut state = 0;
+
+        text.as_ref().iter().any(|&b| {
+            state = self.next_state(state, b);
+            self.nodes[state].output.is_some() || self.nodes[state].dict.is_some()
+        })
+    }
+
+    /// Start a streaming search, where the text is fed chunk by chunk and
+    /// spans are reported relative to the start of the whole stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::{AhoCorasick, MatchKind, PatternMatch};
+    ///
+    /// let ac = AhoCorasick::new(&["timeout"], MatchKind::All, false);
+    /// let mut stream = ac.stream();
+    ///
+    /// assert_eq!(stream.feed("request time"), vec![]);
+    /// assert_eq!(stream.feed("out!"), vec![PatternMatch { pattern: 0, start: 8, end: 15 }]);
+    /// assert_eq!(stream.finish(), vec![]);
+    /// ```
+    pub fn stream(&self) -> AhoCorasickStream<'_> {
+        AhoCorasickStream { automaton: self, state: 0, offset: 0, pending: vec![], min_start: 0 }
+    }
+
+    fn next_state(&self, mut state: usize, b: u8) -> usize {
+        let b = if self.ascii_case_insensitive { b.to_ascii_lowercase() } else { b };
+
+        loop {
+            if let Some(next) = self.nodes[state].child(b) {
+                return next;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.nodes[state].fail;
+        }
+    }
+
+    /// Return the matches ending at `end` in the given state, longest first.
+    fn outputs(&self, state: usize, end: usize) -> impl Iterator<Item = PatternMatch> + '_ {
+        let first = if self.nodes[state].output.is_some() { Some(state) } else { self.nodes[state].dict };
+
+        std::iter::successors(first, |&node| self.nodes[node].dict).map(move |node| {
+            let pattern = self.nodes[node].output.unwrap();
+            PatternMatch { pattern, start: end - self.lens[pattern], end }
+        })
+    }
+}
+
+/// A search in progress, created by [`AhoCorasick::stream`].
+///
+/// Under [`MatchKind::LeftmostLongest`] a match is only reported once no
+/// longer or earlier match can still appear, so it may be returned by a later
+/// call to `feed` or by `finish`.
+#[derive(Debug, Clone)]
+pub struct AhoCorasickStream<'a> {
+    automaton: &'a AhoCorasick,
+    state: usize,
+    offset: usize,
+    pending: Vec<PatternMatch>,
+    min_start: usize,
+}
+
+impl AhoCorasickStream<'_> {
+    /// Feed the next chunk of text and return the matches that became final.
+    pub fn feed<T: AsRef<[u8]>>(&mut self, chunk: T) -> Vec<PatternMatch> {
+        let mut result = vec![];
+
+        for &b in chunk.as_ref() {
+            self.state = self.automaton.next_state(self.state, b);
+            self.offset += 1;
+
+            let found = self.automaton.outputs(self.state, self.offset);
+
+            match self.automaton.kind {
+                MatchKind::All => result.extend(found),
+                MatchKind::LeftmostLongest => {
+                    let min_start = self.min_start;
+                    self.pending.extend(found.filter(|m| m.start >= min_start));
+
+                    // Matches found later start at or after this offset.
+                    let horizon = self.offset - self.automaton.nodes[self.state].depth;
+                    self.flush(horizon, &mut result);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// End the stream and return the matches that were still pending.
+    pub fn finish(mut self) -> Vec<PatternMatch> {
+        let mut result = vec![];
+
+        self.flush(usize::MAX, &mut result);
+        result
+    }
+
+    fn flush(&mut self, horizon: usize, result: &mut Vec<PatternMatch>) {
+        while let Some(best) = self
+            .pending
+            .iter()
+            .copied()
+            .min_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)).then(a.pattern.cmp(&b.pattern)))
+        {
+            if best.start >= horizon {
+                break;
+            }
+
+            result.push(best);
+            self.min_start = best.end;
+            self.pending.retain(|m| m.start >= best.end);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+    use super::*;
+
+    fn spans(matches: Vec<PatternMatch>) -> Vec<(usize, usize, usize)> {
+        matches.into_iter().map(|m| (m.pattern, m.start, m.end)).collect()
+    }
+
+    #[test]
+    fn test_find_all() {
+        let ac = AhoCorasick::new(&["a", "ab", "bcd", "c"], MatchKind::All, false);
+        let answer = spans(ac.find_all("abcd"));
+        assert_eq!(answer, vec![(0, 0, 1), (1, 0, 2), (3, 2, 3), (2, 1, 4)]);
+
+        let ac = AhoCorasick::new(&["a", "abcdef", "cd"], MatchKind::LeftmostLongest, false);
+        let answer = spans(ac.find_all("abcdx"));
+        assert_eq!(answer, vec![(0, 0, 1), (2, 2, 4)]);
+
+        let answer = spans(ac.find_all("abcdefcd"));
+        assert_eq!(answer, vec![(1, 0, 6), (2, 6, 8)]);
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        let ac = AhoCorasick::new(&["Кек", "LOL"], MatchKind::All, true);
+        let answer = spans(ac.find_all("lol Кек кек"));
+        assert_eq!(answer, vec![(1, 0, 3), (0, 4, 10)]);
+
+        let ac = AhoCorasick::new(&["LOL"], MatchKind::All, false);
+        assert_eq!(ac.is_match("lol"), false);
+    }
+
+    #[test]
+    fn test_stream() {
+        let ac = AhoCorasick::new(&["abc", "abcdef", "def"], MatchKind::LeftmostLongest, false);
+        let mut stream = ac.stream();
+
+        assert_eq!(stream.feed("xxab"), vec![]);
+        assert_eq!(stream.feed("cde"), vec![]);
+        assert_eq!(spans(stream.feed("fgdef")), vec![(1, 2, 8)]);
+        assert_eq!(spans(stream.finish()), vec![(2, 9, 12)]);
+    }
+
+    #[test]
+    fn test_duplicates_and_empty() {
+        let ac = AhoCorasick::new(&["", "ab", "ab"], MatchKind::All, false);
+        let answer = spans(ac.find_all("abab"));
+        assert_eq!(answer, vec![(1, 0, 2), (1, 2, 4)]);
+        assert_eq!(ac.patterns_len(), 3);
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::{HashMap, HashSet};
+
+use super::words;
+
+/// Common English words that carry little meaning in frequency reports.
+pub const ENGLISH_STOP_WORDS: &[&str] = &[
+    "a", "about", "after", "all", "also", "an", "and", "any", "are", "as", "at", "be", "because", "been", "but", "by",
+    "can", "could", "did", "do", "does", "for", "from", "had", "has", "have", "he", "her", "his", "how", "i", "if",
+    "in", "into", "is", "it", "its", "just", "me", "my", "no", "not", "of", "on", "or", "our", "out", "she", "so",
+    "than", "that", "the", "their", "them", "then", "there", "these", "they", "this", "to", "up", "us", "was", "we",
+    "were", "what", "when", "which", "who", "will", "with", "would", "you", "your",
+];
+
+/// Return the lowercase words of the text.
+fn lowercase_words(text: &str) -> Vec<String> {
+    words(text).into_iter().map(str::to_lowercase).collect()
+}
+
+/// Return how many times every word occurs, ignoring case and the stop
+/// words. Words are lowercased.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{word_frequencies, ENGLISH_STOP_WORDS};
+///
+/// let answer = word_frequencies("The cat and the hat. The CAT!", ENGLISH_STOP_WORDS);
+/// assert_eq!(answer.get("cat"), Some(&2));
+/// assert_eq!(answer.get("hat"), Some(&1));
+/// assert_eq!(answer.get("the"), None);
+/// ```
+pub fn word_frequencies(text: &str, stop_words: &[&str]) -> HashMap<String, usize> {
+    let stop_words: HashSet<String> = stop_words.iter().map(|w| w.to_lowercase()).collect();
+    let mut hm = HashMap::new();
+
+    for word in lowercase_words(text) {
+        if !stop_words.contains(&word) {
+            *hm.entry(word).or_insert(0) += 1;
+        }
+    }
+
+    hm
+}
+
+/// Return the `k` most frequent words with their counts, most frequent
+/// first and alphabetically among equal counts.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::top_words;
+///
+/// let answer = top_words("мама мыла раму, мама мыла окно", 2, &[]);
+/// assert_eq!(answer, vec![("мама".to_string(), 2), ("мыла".to_string(), 2)]);
+/// ```
+pub fn top_words(text: &str, k: usize, stop_words: &[&str]) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = word_frequencies(text, stop_words).into_iter().collect();
+
+    counts.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(k);
+    counts
+}
+
+/// Return how many times every sequence of `n` consecutive chars occurs.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::char_ngrams;
+///
+/// let answer = char_ngrams("abab", 2);
+/// assert_eq!(answer.get("ab"), Some(&2));
+/// assert_eq!(answer.get("ba"), Some(&1));
+/// assert_eq!(answer.len(), 2);
+/// ```
+pub fn char_ngrams(text: &str, n: usize) -> HashMap<String, usize> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut hm = HashMap::new();
+
+    if n == 0 {
+        return hm;
+    }
+
+    for window in chars.windows(n) {
+        *hm.entry(window.iter().collect()).or_insert(0) += 1;
+    }
+
+    hm
+}
+
+/// Return how many times every sequence of `n` consecutive lowercase words
+/// occurs. The words of an n-gram are joined by a space.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::word_ngrams;
+///
+/// let answer = word_ngrams("to be or not to be", 2);
+/// assert_eq!(answer.get("to be"), Some(&2));
+/// assert_eq!(answer.get("be or"), Some(&1));
+/// assert_eq!(answer.len(), 4);
+/// ```
+pub fn word_ngrams(text: &str, n: usize) -> HashMap<String, usize> {
+    let words = lowercase_words(text);
+    let mut hm = HashMap::new();
+
+    if n == 0 {
+        return hm;
+    }
+
+    for window in words.windows(n) {
+        *hm.entry(window.join(" ")).or_insert(0) += 1;
+    }
+
+    hm
+}
+
+/// Return the type/token ratio, i.e. the number of distinct words divided by
+/// the number of words, ignoring case. Return `0.0` for a text without words.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::type_token_ratio;
+///
+/// let answer = type_token_ratio("the cat saw The dog");
+/// assert_eq!(answer, 0.8);
+/// ```
+pub fn type_token_ratio(text: &str) -> f64 {
+    let words = lowercase_words(text);
+
+    if words.is_empty() {
+        return 0.0;
+    }
+
+    let types: HashSet<&String> = words.iter().collect();
+
+    types.len() as f64 / words.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_frequencies() {
+        assert_eq!(word_frequencies("", ENGLISH_STOP_WORDS), HashMap::new());
+
+        let answer = word_frequencies("It's a dog's life, isn't it?", &["IT", "a"]);
+        let expected: HashMap<String, usize> =
+            [("it's", 1), ("dog's", 1), ("life", 1), ("isn't", 1)].map(|(w, c)| (w.to_string(), c)).into();
+        assert_eq!(answer, expected);
+    }
+
+    #[test]
+    fn test_top_words() {
+        let text = "one two two three three three, ONE two";
+        assert_eq!(top_words(text, 0, &[]), vec![]);
+        assert_eq!(
+            top_words(text, 10, &["three"]),
+            vec![("two".to_string(), 3), ("one".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn test_ngrams() {
+        assert_eq!(char_ngrams("ab", 3), HashMap::new());
+        assert_eq!(char_ngrams("ab", 0), HashMap::new());
+        assert_eq!(char_ngrams("日本日本", 2).get("日本"), Some(&2));
+        assert_eq!(word_ngrams("a b", 3), HashMap::new());
+        assert_eq!(word_ngrams("Привет мир, привет МИР", 2).get("привет мир"), Some(&2));
+    }
+
+    #[test]
+    fn test_type_token_ratio() {
+        assert_eq!(type_token_ratio(""), 0.0);
+        assert_eq!(type_token_ratio("... !!!"), 0.0);
+        assert_eq!(type_token_ratio("a A a a"), 0.25);
+    }
+}
@@ -0,0 +1,1380 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use super::CharSource;
+
+/// The bracket pairs of [`BracketValidator::new`] and the free functions.
+const PAIRS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+
+/// The kind of a [`BracketError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BracketErrorKind {
+    /// A closing bracket without any open bracket.
+    UnexpectedCloser(char),
+    /// A closing bracket that doesn't match the innermost open bracket.
+    Mismatch { expected: char, found: char },
+    /// An open bracket that is never closed.
+    UnclosedOpener(char),
+    /// A string or block comment that is never closed.
+    UnclosedRegion,
+}
+
+/// The first error found by [`BracketValidator::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BracketError {
+    /// Char index in the text where the problem was found.
+    pub position: usize,
+    pub kind: BracketErrorKind,
+}
+
+impl fmt::Display for BracketError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            BracketErrorKind::UnexpectedCloser(c) => write!(f, "unexpected closing bracket `{}`", c)?,
+            BracketErrorKind::Mismatch { expected, found } => {
+                write!(f, "mismatched bracket: expected `{}`, found `{}`", expected, found)?
+            }
+            BracketErrorKind::UnclosedOpener(c) => write!(f, "unclosed bracket `{}`", c)?,
+            BracketErrorKind::UnclosedRegion => write!(f, "unclosed string or comment")?,
+        }
+
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl std::error::Error for BracketError {}
+
+/// A string or comment whose content is not checked for brackets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Region {
+    open: Vec<char>,
+    close: Vec<char>,
+    escape: Option<char>,
+    is_closed_by_end: bool,
+}
+
+/// A bracket checker with configurable bracket pairs, ignored chars, and
+/// string and comment regions. Chars other than brackets are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{BracketErrorKind, BracketValidator};
+///
+/// let validator = BracketValidator::new().string('"', Some('\\')).line_comment("//");
+///
+/// let answer = validator.validate(r#"f("(", a[0]) // :)"#);
+/// assert_eq!(answer, Ok(()));
+///
+/// let answer = validator.validate("f(a[0)]").unwrap_err();
+/// assert_eq!(answer.kind, BracketErrorKind::Mismatch { expected: ']', found: ')' });
+/// assert_eq!(answer.position, 5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BracketValidator {
+    pairs: Vec<(char, char)>,
+    ignored: Vec<char>,
+    regions: Vec<Region>,
+}
+
+impl Default for BracketValidator {
+    fn default() -> Self {
+        BracketValidator { pairs: PAIRS.to_vec(), ignored: vec![], regions: vec![] }
+    }
+}
+
+impl BracketValidator {
+    /// Create a validator of `()`, `[]` and `{}` without strings and comments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the bracket pairs. A pair may use the same char to open and
+    /// close, like `|`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::BracketValidator;
+    ///
+    /// let validator = BracketValidator::new().pairs(&[('<', '>'), ('|', '|')]);
+    ///
+    /// assert_eq!(validator.validate("<|x|> (").is_ok(), true);
+    /// assert_eq!(validator.validate("<|x>|").is_ok(), false);
+    /// ```
+    pub fn pairs(mut self, pairs: &[(char, char)]) -> Self {
+        self.pairs = pairs.to_vec();
+        self
+    }
+
+    /// Never treat the chars of `chars` as brackets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::BracketValidator;
+    ///
+    /// let validator = BracketValidator::new().pairs(&[('(', ')'), ('<', '>')]).ignore("<>");
+    ///
+    /// assert_eq!(validator.validate("(a < b)").is_ok(), true);
+    /// ```
+    pub fn ignore(mut self, chars: &str) -> Self {
+        self.ignored.extend(chars.chars());
+        self
+    }
+
+    /// Add a string quoted by `quote` on both sides. An `escape` char makes
+    /// the next char part of the string.
+    pub fn string(self, quote: char, escape: Option<char>) -> Self {
+        self.region(vec![quote], vec![quote], escape, false)
+    }
+
+    /// Add a comment from `open` to the end of the line or of the text.
+    pub fn line_comment(self, open: &str) -> Self {
+        self.region(open.chars().collect(), vec!['\n'], None, true)
+    }
+
+    /// Add a comment from `open` to `close`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::{BracketErrorKind, BracketValidator};
+    ///
+    /// let validator = BracketValidator::new().block_comment("(*", "*)");
+    ///
+    /// assert_eq!(validator.validate("f (x) (* ) *)"), Ok(()));
+    /// assert_eq!(validator.validate("f (* ) ").unwrap_err().kind, BracketErrorKind::UnclosedRegion);
+    /// ```
+    pub fn block_comment(self, open: &str, close: &str) -> Self {
+        self.region(open.chars().collect(), close.chars().collect(), None, false)
+    }
+
+    fn region(mut self, open: Vec<char>, close: Vec<char>, escape: Option<char>, is_closed_by_end: bool) -> Self {
+        if !open.is_empty() && !close.is_empty() {
+            self.regions.push(Region { open, close, escape, is_closed_by_end });
+        }
+
+        self
+    }
+
+    /// Return the first bracket error of the text, if any. A text ends with
+    /// an error for the earliest open bracket that is never closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::{BracketError, BracketErrorKind, BracketValidator};
+    ///
+    /// let validator = BracketValidator::new();
+    ///
+    /// let answer = validator.validate("(a))");
+    /// assert_eq!(answer, Err(BracketError { position: 3, kind: BracketErrorKind::UnexpectedCloser(')') }));
+    ///
+    /// let answer = validator.validate("([a]");
+    /// assert_eq!(answer, Err(BracketError { position: 0, kind: BracketErrorKind::UnclosedOpener('(') }));
+    /// ```
+    pub fn validate<S: CharSource + ?Sized>(&self, s: &S) -> Result<(), BracketError> {
+        let mut scanner = Scanner::default();
+
+        for c in s.source_chars() {
+            scanner.push(self, c)?;
+        }
+
+        scanner.finish(self)
+    }
+
+    /// Start a check of a text given in chunks, such as a file read piece by
+    /// piece or a network stream. Only the open brackets are kept in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::{BracketError, BracketErrorKind, BracketValidator};
+    ///
+    /// let validator = BracketValidator::new().string('"', Some('\\'));
+    /// let mut stream = validator.stream();
+    ///
+    /// assert_eq!(stream.feed(r#"{"a": ["#), Ok(()));
+    /// assert_eq!(stream.feed(r#"1, "]"]}"#), Ok(()));
+    /// assert_eq!(stream.finish(), Ok(()));
+    ///
+    /// let mut stream = validator.stream();
+    ///
+    /// assert_eq!(stream.feed("[[1, 2"), Ok(()));
+    /// let answer = stream.feed("], 3}");
+    /// assert_eq!(answer, Err(BracketError { position: 10, kind: BracketErrorKind::Mismatch { expected: ']', found: '}' } }));
+    /// ```
+    pub fn stream(&self) -> BracketStream<'_> {
+        BracketStream { validator: self, scanner: Scanner::default(), error: None }
+    }
+}
+
+/// A check in progress, created by [`BracketValidator::stream`].
+///
+/// Errors are reported by the call to `feed` whose chunk contains them,
+/// except when a bracket may start a comment and the next chunk decides.
+/// After an error every call returns the same error.
+#[derive(Debug, Clone)]
+pub struct BracketStream<'a> {
+    validator: &'a BracketValidator,
+    scanner: Scanner,
+    error: Option<BracketError>,
+}
+
+impl BracketStream<'_> {
+    /// Feed the next chunk of text and return the first error found so far.
+    pub fn feed(&mut self, chunk: &str) -> Result<(), BracketError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        for c in chunk.chars() {
+            if let Err(error) = self.scanner.push(self.validator, c) {
+                self.error = Some(error);
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the number of chars fed so far.
+    pub fn position(&self) -> usize {
+        self.scanner.position
+    }
+
+    /// Return the number of brackets that are open.
+    pub fn depth(&self) -> usize {
+        self.scanner.stack.len()
+    }
+
+    /// End the stream and return the first error of the whole text.
+    pub fn finish(mut self) -> Result<(), BracketError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => self.scanner.finish(self.validator),
+        }
+    }
+}
+
+/// A string or comment the scanner is inside of.
+#[derive(Debug, Clone)]
+struct ActiveRegion {
+    index: usize,
+    start: usize,
+    tail: VecDeque<char>,
+    is_escaped: bool,
+}
+
+/// The state of a check, fed one char at a time. It keeps the open brackets
+/// and at most a delimiter's worth of chars.
+#[derive(Debug, Clone, Default)]
+struct Scanner {
+    position: usize,
+    stack: Vec<(char, char, usize)>,
+    pending: Vec<(char, usize)>,
+    region: Option<ActiveRegion>,
+}
+
+impl Scanner {
+    fn push(&mut self, validator: &BracketValidator, c: char) -> Result<(), BracketError> {
+        self.position += 1;
+        self.step(validator, c, self.position - 1)
+    }
+
+    fn finish(&mut self, validator: &BracketValidator) -> Result<(), BracketError> {
+        self.resolve(validator, true)?;
+
+        if let Some(region) = &self.region {
+            if !validator.regions[region.index].is_closed_by_end {
+                return Err(BracketError { position: region.start, kind: BracketErrorKind::UnclosedRegion });
+            }
+        }
+
+        match self.stack.first() {
+            Some(&(open, _, position)) => Err(BracketError { position, kind: BracketErrorKind::UnclosedOpener(open) }),
+            None => Ok(()),
+        }
+    }
+
+    fn step(&mut self, validator: &BracketValidator, c: char, position: usize) -> Result<(), BracketError> {
+        let Some(region) = &mut self.region else {
+            self.pending.push((c, position));
+            return self.resolve(validator, false);
+        };
+        let close = &validator.regions[region.index].close;
+
+        if region.is_escaped {
+            region.is_escaped = false;
+        } else if Some(c) == validator.regions[region.index].escape {
+            region.is_escaped = true;
+            region.tail.clear();
+        } else {
+            region.tail.push_back(c);
+
+            if region.tail.len() > close.len() {
+                region.tail.pop_front();
+            }
+
+            if region.tail.iter().eq(close.iter()) {
+                self.region = None;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handle the pending chars that can no longer start a region. Unless
+    /// it's the end of the text, wait while they are a prefix of an opener.
+    fn resolve(&mut self, validator: &BracketValidator, is_end: bool) -> Result<(), BracketError> {
+        while let Some(&(_, start)) = self.pending.first() {
+            if self.region.is_some() {
+                for (c, position) in std::mem::take(&mut self.pending) {
+                    self.step(validator, c, position)?;
+                }
+
+                continue;
+            }
+
+            let chars: Vec<char> = self.pending.iter().map(|&(c, _)| c).collect();
+            let is_prefix = validator.regions.iter().any(|r| r.open.len() > chars.len() && r.open.starts_with(&chars));
+
+            if is_prefix && !is_end {
+                return Ok(());
+            }
+
+            let opener = (0..validator.regions.len())
+                .filter(|&i| chars.starts_with(&validator.regions[i].open))
+                .max_by_key(|&i| validator.regions[i].open.len());
+
+            match opener {
+                Some(index) => {
+                    self.pending.drain(..validator.regions[index].open.len());
+                    self.region = Some(ActiveRegion { index, start, tail: VecDeque::new(), is_escaped: false });
+                }
+                None => {
+                    let (c, position) = self.pending.remove(0);
+                    self.bracket(validator, c, position)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn bracket(&mut self, validator: &BracketValidator, c: char, position: usize) -> Result<(), BracketError> {
+        if validator.ignored.contains(&c) {
+            return Ok(());
+        }
+
+        if matches!(self.stack.last(), Some(&(_, close, _)) if close == c) {
+            self.stack.pop();
+        } else if let Some(&(open, close)) = validator.pairs.iter().find(|&&(open, _)| open == c) {
+            self.stack.push((open, close, position));
+        } else if validator.pairs.iter().any(|&(_, close)| close == c) {
+            let kind = match self.stack.pop() {
+                Some((_, expected, _)) => BracketErrorKind::Mismatch { expected, found: c },
+                None => BracketErrorKind::UnexpectedCloser(c),
+            };
+
+            return Err(BracketError { position, kind });
+        }
+
+        Ok(())
+    }
+}
+
+/// An edit made by [`repair`]. Positions are char indices in the original
+/// text; an insertion goes before the char at its position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BracketEdit {
+    Insert { position: usize, bracket: char },
+    Delete { position: usize },
+    Replace { position: usize, bracket: char },
+}
+
+impl BracketEdit {
+    fn position(&self) -> usize {
+        match *self {
+            BracketEdit::Insert { position, .. }
+            | BracketEdit::Delete { position }
+            | BracketEdit::Replace { position, .. } => position,
+        }
+    }
+}
+
+fn closer_of(c: char) -> Option<char> {
+    PAIRS.iter().find(|&&(open, _)| open == c).map(|&(_, close)| close)
+}
+
+fn opener_of(c: char) -> Option<char> {
+    PAIRS.iter().find(|&&(_, close)| close == c).map(|&(open, _)| open)
+}
+
+/// Return the pairs of char indices of matching `()`, `[]` and `{}`, ordered
+/// by the open bracket. Closing brackets that don't match the innermost open
+/// bracket are left unmatched.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::match_brackets;
+///
+/// let answer = match_brackets("f(a[0]) }");
+/// assert_eq!(answer, vec![(1, 6), (3, 5)]);
+///
+/// let answer = match_brackets("(]) ((");
+/// assert_eq!(answer, vec![(0, 2)]);
+/// ```
+pub fn match_brackets<S: CharSource + ?Sized>(s: &S) -> Vec<(usize, usize)> {
+    let mut stack = vec![];
+    let mut result = vec![];
+
+    for (i, c) in s.source_chars().enumerate() {
+        if let Some(close) = closer_of(c) {
+            stack.push((i, close));
+        } else if matches!(stack.last(), Some(&(_, close)) if close == c) {
+            result.push((stack.pop().unwrap().0, i));
+        }
+    }
+
+    result.sort_unstable();
+    result
+}
+
+/// Return the length in chars of the longest substring whose brackets are
+/// balanced.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::longest_valid_parentheses;
+///
+/// let answer = longest_valid_parentheses(")()())");
+/// assert_eq!(answer, 4);
+///
+/// let answer = longest_valid_parentheses("{(x]} [a](b)");
+/// assert_eq!(answer, 7);
+/// ```
+pub fn longest_valid_parentheses<S: CharSource + ?Sized>(s: &S) -> usize {
+    let mut stack: Vec<(usize, char)> = vec![];
+    let mut start = 0;
+    let mut result = 0;
+
+    for (i, c) in s.source_chars().enumerate() {
+        if let Some(close) = closer_of(c) {
+            stack.push((i, close));
+        } else if opener_of(c).is_some() {
+            if matches!(stack.last(), Some(&(_, close)) if close == c) {
+                stack.pop();
+            } else {
+                stack.clear();
+                start = i + 1;
+            }
+        }
+
+        let from = stack.last().map_or(start, |&(j, _)| j + 1);
+        result = result.max(i + 1 - from);
+    }
+
+    result
+}
+
+/// Largest number of brackets, left after cancelling matched pairs, that
+/// [`min_insertions`] and [`repair`] balance with the O(m³) interval DP.
+const MAX_BALANCER: usize = 500;
+
+/// Return the brackets of the text with their char offsets, after
+/// repeatedly cancelling an open bracket directly followed by its closing
+/// one, and the length of the text. Cancelling such a pair doesn't change
+/// the fewest edits, so the cancelled pairs are left as they are.
+fn reduce<S: CharSource + ?Sized>(s: &S) -> (Vec<(usize, char)>, usize) {
+    let mut brackets: Vec<(usize, char)> = vec![];
+    let mut len = 0;
+
+    for (i, c) in s.source_chars().enumerate() {
+        len += 1;
+
+        if matches!(brackets.last(), Some(&(_, open)) if closer_of(open) == Some(c)) {
+            brackets.pop();
+        } else if closer_of(c).is_some() || opener_of(c).is_some() {
+            brackets.push((i, c));
+        }
+    }
+
+    (brackets, len)
+}
+
+/// The brackets left unmatched by a stack pass. A closing bracket that
+/// mismatches the innermost open bracket closes it anyway, and the pass is
+/// only minimal when there is no such bracket.
+struct Unmatched {
+    closers: Vec<(usize, char)>,
+    openers: Vec<(usize, char)>,
+    // The mismatched closing brackets and the brackets that would match.
+    mismatched: Vec<(usize, char)>,
+    len: usize,
+}
+
+impl Unmatched {
+    fn new(brackets: &[(usize, char)], len: usize) -> Self {
+        let mut unmatched = Unmatched { closers: vec![], openers: vec![], mismatched: vec![], len };
+
+        for &(i, c) in brackets {
+            if closer_of(c).is_some() {
+                unmatched.openers.push((i, c));
+            } else {
+                match unmatched.openers.pop() {
+                    Some((_, open)) if closer_of(open) == Some(c) => {}
+                    Some((_, open)) => unmatched.mismatched.push((i, closer_of(open).unwrap())),
+                    None => unmatched.closers.push((i, c)),
+                }
+            }
+        }
+
+        unmatched
+    }
+
+    /// Return the insertions: two for every mismatched closing bracket, for
+    /// the bracket it closes and for itself.
+    fn insertions(&self) -> usize {
+        self.closers.len() + self.openers.len() + 2 * self.mismatched.len()
+    }
+
+    /// Return the edits, in order. Every unexpected closing bracket is at
+    /// depth 0 and every unclosed one is nested in the previous one, so two
+    /// of them make a pair with one replacement, and one left over is
+    /// deleted or closed at the end.
+    fn edits(&self) -> Vec<BracketEdit> {
+        let mut edits: Vec<BracketEdit> =
+            self.mismatched.iter().map(|&(position, bracket)| BracketEdit::Replace { position, bracket }).collect();
+
+        for pair in self.closers.chunks(2) {
+            edits.push(match *pair {
+                [(position, _), (_, close)] => BracketEdit::Replace { position, bracket: opener_of(close).unwrap() },
+                _ => BracketEdit::Delete { position: pair[0].0 },
+            });
+        }
+
+        for pair in self.openers.chunks(2) {
+            edits.push(match *pair {
+                [(_, open), (position, _)] => BracketEdit::Replace { position, bracket: closer_of(open).unwrap() },
+                _ => BracketEdit::Insert { position: self.len, bracket: closer_of(pair[0].1).unwrap() },
+            });
+        }
+
+        edits.sort_by_key(BracketEdit::position);
+        edits
+    }
+}
+
+/// The fewest edits that balance the brackets of a text, found by an
+/// interval DP over its `m` brackets in O(m³) time and O(m²) memory.
+struct Balancer {
+    brackets: Vec<(usize, char)>,
+    len: usize,
+    allow_replace: bool,
+    // `cost[i][j]` is the cost of balancing brackets `i..j`.
+    cost: Vec<Vec<usize>>,
+}
+
+/// A step of [`Balancer::edits`]: balance a range of brackets, or output an edit.
+enum Step {
+    Range(usize, usize),
+    Edit(BracketEdit),
+}
+
+impl Balancer {
+    fn new(brackets: Vec<(usize, char)>, len: usize, allow_replace: bool) -> Self {
+        let m = brackets.len();
+        let mut balancer = Balancer { brackets, len, allow_replace, cost: vec![vec![0; m + 1]; m + 1] };
+
+        for i in (0..m).rev() {
+            for j in i + 1..=m {
+                let best = balancer.choices(i, j).map(|(cost, _)| cost).min();
+                balancer.cost[i][j] = best.unwrap();
+            }
+        }
+
+        balancer
+    }
+
+    /// Return the cost of pairing bracket `i` with bracket `k`.
+    fn pair_cost(&self, i: usize, k: usize) -> Option<usize> {
+        let (open, close) = (self.brackets[i].1, self.brackets[k].1);
+
+        if closer_of(open) == Some(close) {
+            Some(0)
+        } else if self.allow_replace && (closer_of(open).is_some() || opener_of(close).is_some()) {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    /// Return the costs of leaving bracket `i` unpaired (`None`) and of
+    /// pairing it with every `k` in `i + 1..j`, pairings first.
+    fn choices(&self, i: usize, j: usize) -> impl Iterator<Item = (usize, Option<usize>)> + '_ {
+        let pairings = (i + 1..j).filter_map(move |k| {
+            let cost = self.pair_cost(i, k)? + self.cost[i + 1][k] + self.cost[k + 1][j];
+            Some((cost, Some(k)))
+        });
+
+        pairings.chain(std::iter::once((1 + self.cost[i + 1][j], None)))
+    }
+
+    /// Return the edits of the cheapest choices, inner brackets first.
+    fn edits(&self) -> Vec<BracketEdit> {
+        let mut edits = vec![];
+        let mut steps = vec![Step::Range(0, self.brackets.len())];
+
+        while let Some(step) = steps.pop() {
+            let (i, j) = match step {
+                Step::Edit(edit) => {
+                    edits.push(edit);
+                    continue;
+                }
+                Step::Range(i, j) if i >= j => continue,
+                Step::Range(i, j) => (i, j),
+            };
+
+            let (position, c) = self.brackets[i];
+            let (_, choice) = self.choices(i, j).find(|&(cost, _)| cost == self.cost[i][j]).unwrap();
+
+            // Steps run in reverse order of pushing.
+            match choice {
+                Some(k) => {
+                    steps.push(Step::Range(k + 1, j));
+
+                    if self.pair_cost(i, k) != Some(0) {
+                        steps.push(Step::Edit(match closer_of(c) {
+                            Some(close) => BracketEdit::Replace { position: self.brackets[k].0, bracket: close },
+                            None => BracketEdit::Replace { position, bracket: opener_of(self.brackets[k].1).unwrap() },
+                        }));
+                    }
+
+                    steps.push(Step::Range(i + 1, k));
+                }
+                None => {
+                    steps.push(Step::Edit(match closer_of(c) {
+                        Some(close) => {
+                            let end = self.brackets.get(j).map_or(self.len, |&(next, _)| next);
+                            BracketEdit::Insert { position: end, bracket: close }
+                        }
+                        None => BracketEdit::Delete { position },
+                    }));
+                    steps.push(Step::Range(i + 1, j));
+                }
+            }
+        }
+
+        edits.sort_by_key(BracketEdit::position);
+        edits
+    }
+}
+
+/// Return the fewest brackets to insert to balance `()`, `[]` and `{}`.
+///
+/// # Performance
+///
+/// Matched pairs are cancelled in O(n) time for a text of `n` chars. If a
+/// closing bracket left mismatches an open one, an interval DP over the `m`
+/// brackets left takes O(m³) time and O(m²) memory. Above 500 such brackets
+/// a linear stack pass is used instead, which balances the text but may
+/// make more edits than needed.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::min_insertions;
+///
+/// let answer = min_insertions("(()");
+/// assert_eq!(answer, 1);
+///
+/// let answer = min_insertions("([)");
+/// assert_eq!(answer, 1);
+/// ```
+pub fn min_insertions<S: CharSource + ?Sized>(s: &S) -> usize {
+    let (brackets, len) = reduce(s);
+    let unmatched = Unmatched::new(&brackets, len);
+
+    if unmatched.mismatched.is_empty() || brackets.len() > MAX_BALANCER {
+        return unmatched.insertions();
+    }
+
+    let m = brackets.len();
+
+    Balancer::new(brackets, len, false).cost[0][m]
+}
+
+/// Return the text with balanced `()`, `[]` and `{}` made with the fewest
+/// insertions, deletions and replacements of brackets, and these edits in
+/// order. An unclosed bracket is closed before the next bracket outside of
+/// it, and an unexpected closing bracket is deleted.
+///
+/// # Performance
+///
+/// Matched pairs are cancelled in O(n) time for a text of `n` chars. If a
+/// closing bracket left mismatches an open one, an interval DP over the `m`
+/// brackets left takes O(m³) time and O(m²) memory. Above 500 such brackets
+/// a linear stack pass is used instead, which balances the text but may
+/// make more edits than needed.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{repair, BracketEdit};
+///
+/// let (answer, edits) = repair("f(a[0)");
+/// assert_eq!(answer, "f(a[0])");
+/// assert_eq!(edits, vec![BracketEdit::Insert { position: 5, bracket: ']' }]);
+///
+/// let (answer, edits) = repair("{a]) b");
+/// assert_eq!(answer, "{a} b");
+/// assert_eq!(edits, vec![BracketEdit::Replace { position: 2, bracket: '}' }, BracketEdit::Delete { position: 3 }]);
+/// ```
+pub fn repair<S: CharSource + ?Sized>(s: &S) -> (String, Vec<BracketEdit>) {
+    if BracketValidator::new().validate(s).is_ok() {
+        return (s.source_chars().collect(), vec![]);
+    }
+
+    let (brackets, len) = reduce(s);
+    let unmatched = Unmatched::new(&brackets, len);
+    let edits = if unmatched.mismatched.is_empty() || brackets.len() > MAX_BALANCER {
+        unmatched.edits()
+    } else {
+        Balancer::new(brackets, len, true).edits()
+    };
+
+    let mut result = String::new();
+    let mut pending = edits.iter().peekable();
+
+    for (i, c) in s.source_chars().enumerate() {
+        let mut keep = true;
+
+        while let Some(edit) = pending.next_if(|edit| edit.position() == i) {
+            match *edit {
+                BracketEdit::Insert { bracket, .. } => result.push(bracket),
+                BracketEdit::Delete { .. } => keep = false,
+                BracketEdit::Replace { bracket, .. } => {
+                    result.push(bracket);
+                    keep = false;
+                }
+            }
+        }
+
+        if keep {
+            result.push(c);
+        }
+    }
+
+    // Only insertions are left, at the end of the text.
+    for edit in pending {
+        if let BracketEdit::Insert { bracket, .. } = *edit {
+            result.push(bracket);
+        }
+    }
+
+    (result, edits)
+}
+
+/// An iterator over the balanced strings of `n` bracket pairs, created by
+/// [`balanced_brackets`] and [`balanced_brackets_with`].
+///
+/// Strings come in the order of [`rank_balanced`]: an open bracket sorts
+/// before a closing one, and pairs sort in the order they are given.
+#[derive(Debug, Clone)]
+pub struct BalancedBrackets {
+    pairs: Vec<(char, char)>,
+    // The kind of every open bracket, or `pairs.len()` for a closing one.
+    tokens: Option<Vec<usize>>,
+}
+
+impl BalancedBrackets {
+    fn new(n: usize, pairs: &[(char, char)]) -> Self {
+        let k = pairs.len();
+        let tokens = if n > 0 && k == 0 { None } else { Some([vec![0; n], vec![k; n]].concat()) };
+
+        BalancedBrackets { pairs: pairs.to_vec(), tokens }
+    }
+
+    /// Return the tokens of the next string, changing the rightmost token
+    /// that can grow and filling the rest with the smallest tokens.
+    fn successor(&self, tokens: &[usize]) -> Option<Vec<usize>> {
+        let (n, k) = (tokens.len() / 2, self.pairs.len());
+        let mut opens = tokens.iter().filter(|&&t| t < k).count();
+        let mut depth = 0;
+
+        for i in (0..tokens.len()).rev() {
+            if tokens[i] < k {
+                opens -= 1;
+                depth -= 1;
+            } else {
+                depth += 1;
+            }
+
+            let next = (tokens[i] + 1..=k).find(|&t| if t < k { opens < n } else { depth > 0 });
+
+            if let Some(t) = next {
+                let mut result = tokens[..i].to_vec();
+                result.push(t);
+                opens += usize::from(t < k);
+
+                for _ in i + 1..tokens.len() {
+                    if opens < n {
+                        result.push(0);
+                        opens += 1;
+                    } else {
+                        result.push(k);
+                    }
+                }
+
+                return Some(result);
+            }
+        }
+
+        None
+    }
+}
+
+impl Iterator for BalancedBrackets {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let tokens = self.tokens.take()?;
+        let mut stack = vec![];
+        let mut result = String::new();
+
+        for &t in &tokens {
+            match self.pairs.get(t) {
+                Some(&(open, _)) => {
+                    stack.push(t);
+                    result.push(open);
+                }
+                None => result.push(self.pairs[stack.pop().unwrap()].1),
+            }
+        }
+
+        self.tokens = self.successor(&tokens);
+        Some(result)
+    }
+}
+
+/// Return an iterator over the balanced strings of `n` pairs of `()`.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::balanced_brackets;
+///
+/// let answer: Vec<String> = balanced_brackets(3).collect();
+/// assert_eq!(answer, vec!["((()))", "(()())", "(())()", "()(())", "()()()"]);
+/// ```
+pub fn balanced_brackets(n: usize) -> BalancedBrackets {
+    BalancedBrackets::new(n, &[('(', ')')])
+}
+
+/// Return an iterator over the balanced strings of `n` bracket pairs of any
+/// of the given kinds.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::balanced_brackets_with;
+///
+/// let answer: Vec<String> = balanced_brackets_with(1, &[('(', ')'), ('[', ']')]).collect();
+/// assert_eq!(answer, vec!["()", "[]"]);
+///
+/// let answer = balanced_brackets_with(4, &[('(', ')'), ('[', ']'), ('{', '}')]).count();
+/// assert_eq!(answer, 14 * 81);
+/// ```
+pub fn balanced_brackets_with(n: usize, pairs: &[(char, char)]) -> BalancedBrackets {
+    BalancedBrackets::new(n, pairs)
+}
+
+/// Return `ways[m][d]`, the number of ways to end a balanced string with `m`
+/// more brackets from depth `d`, or `None` if it doesn't fit in `u128`.
+fn completions(n: usize, kinds: usize) -> Vec<Vec<Option<u128>>> {
+    let mut ways = vec![vec![Some(0u128); n + 2]; 2 * n + 1];
+    ways[0][0] = Some(1);
+
+    for m in 1..=2 * n {
+        for d in 0..=n {
+            let open = match ways[m - 1][d + 1] {
+                Some(0) => Some(0),
+                w => w.and_then(|w| w.checked_mul(kinds as u128)),
+            };
+            let close = if d > 0 { ways[m - 1][d - 1] } else { Some(0) };
+
+            ways[m][d] = open.zip(close).and_then(|(open, close)| open.checked_add(close));
+        }
+    }
+
+    ways
+}
+
+/// Return the number of balanced strings of `n` bracket pairs of any of
+/// `kinds` kinds, the Catalan number times `kinds` to the `n`, or `None` if
+/// it doesn't fit in `u128`.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::count_balanced;
+///
+/// let answer = count_balanced(3, 1);
+/// assert_eq!(answer, Some(5));
+///
+/// let answer = count_balanced(3, 2);
+/// assert_eq!(answer, Some(40));
+/// ```
+pub fn count_balanced(n: usize, kinds: usize) -> Option<u128> {
+    let catalan = crate::number::catalan(u32::try_from(n).ok()?)?;
+
+    (kinds as u128).checked_pow(u32::try_from(n).ok()?)?.checked_mul(catalan)
+}
+
+/// Return the index of a balanced string among the strings of
+/// [`balanced_brackets_with`] of its length, or `None` if it is not balanced
+/// over the pairs or the number of those strings, [`count_balanced`], doesn't
+/// fit in `u128`. Takes O(n²) time and memory for `n` pairs, and `n` is at
+/// most 69 when the count fits.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::rank_balanced;
+///
+/// let answer = rank_balanced("(())()", &[('(', ')')]);
+/// assert_eq!(answer, Some(2));
+///
+/// let answer = rank_balanced("[]()", &[('(', ')'), ('[', ']')]);
+/// assert_eq!(answer, Some(6));
+///
+/// let answer = rank_balanced("(]", &[('(', ')'), ('[', ']')]);
+/// assert_eq!(answer, None);
+/// ```
+pub fn rank_balanced<S: CharSource + ?Sized>(s: &S, pairs: &[(char, char)]) -> Option<u128> {
+    let k = pairs.len();
+    let mut stack = vec![];
+    let mut tokens = vec![];
+
+    for c in s.source_chars() {
+        if let Some(kind) = pairs.iter().position(|&(open, _)| open == c) {
+            stack.push(kind);
+            tokens.push(kind);
+        } else if stack.pop().map(|kind| pairs[kind].1) == Some(c) {
+            tokens.push(k);
+        } else {
+            return None;
+        }
+    }
+
+    if !stack.is_empty() {
+        return None;
+    }
+
+    // Checking the count first keeps the table small.
+    count_balanced(tokens.len() / 2, k)?;
+    let ways = completions(tokens.len() / 2, k);
+    let mut rank: u128 = 0;
+    let mut depth = 0;
+
+    for (i, &t) in tokens.iter().enumerate() {
+        // Every open bracket of a smaller kind leads to smaller strings.
+        let smaller = t.min(k) as u128;
+
+        if smaller > 0 {
+            let block = ways[tokens.len() - i - 1][depth + 1]?;
+            rank = rank.checked_add(smaller.checked_mul(block)?)?;
+        }
+
+        if t < k {
+            depth += 1;
+        } else {
+            depth -= 1;
+        }
+    }
+
+    Some(rank)
+}
+
+/// Return the balanced string of `n` bracket pairs with the given index
+/// among the strings of [`balanced_brackets_with`], or `None` if the index is
+/// too large or the number of those strings, [`count_balanced`], doesn't fit
+/// in `u128`. Takes O(n²) time and memory, and `n` is at most 69 when the
+/// count fits.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::unrank_balanced;
+///
+/// let answer = unrank_balanced(3, 2, &[('(', ')')]);
+/// assert_eq!(answer, Some("(())()".to_string()));
+///
+/// let answer = unrank_balanced(2, 6, &[('(', ')'), ('[', ']')]);
+/// assert_eq!(answer, Some("[]()".to_string()));
+///
+/// let answer = unrank_balanced(3, 5, &[('(', ')')]);
+/// assert_eq!(answer, None);
+/// ```
+pub fn unrank_balanced(n: usize, index: u128, pairs: &[(char, char)]) -> Option<String> {
+    if index >= count_balanced(n, pairs.len())? {
+        return None;
+    }
+
+    let ways = completions(n, pairs.len());
+
+    let mut index = index;
+    let mut stack = vec![];
+    let mut result = String::new();
+
+    for i in 0..2 * n {
+        let block = ways[2 * n - i - 1][stack.len() + 1];
+        let mut kind = None;
+
+        for (j, &(open, _)) in pairs.iter().enumerate() {
+            match block {
+                Some(block) if index >= block => index -= block,
+                _ => {
+                    kind = Some(j);
+                    result.push(open);
+                    break;
+                }
+            }
+        }
+
+        match kind {
+            Some(j) => stack.push(j),
+            None => result.push(pairs[stack.pop()?].1),
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::string::{is_valid_parentheses_in, Rope};
+
+    fn error(position: usize, kind: BracketErrorKind) -> Result<(), BracketError> {
+        Err(BracketError { position, kind })
+    }
+
+    #[test]
+    fn test_validate() {
+        let validator = BracketValidator::new();
+
+        assert_eq!(validator.validate(""), Ok(()));
+        assert_eq!(validator.validate("a(b[c]{d})e"), Ok(()));
+        assert_eq!(validator.validate("привет)"), error(6, BracketErrorKind::UnexpectedCloser(')')));
+        assert_eq!(validator.validate("(]"), error(1, BracketErrorKind::Mismatch { expected: ')', found: ']' }));
+        assert_eq!(validator.validate("(()"), error(0, BracketErrorKind::UnclosedOpener('(')));
+        assert_eq!(validator.validate("\"(\""), error(1, BracketErrorKind::UnclosedOpener('(')));
+    }
+
+    #[test]
+    fn test_regions() {
+        let validator = BracketValidator::new()
+            .string('"', Some('\\'))
+            .string('\'', None)
+            .line_comment("//")
+            .block_comment("/*", "*/");
+
+        assert_eq!(validator.validate(r#"f("\")", '\') // ("#), Ok(()));
+        assert_eq!(validator.validate("a /* ( */ [b] / c"), Ok(()));
+        assert_eq!(validator.validate("a /*/ ( */ b"), Ok(()));
+        assert_eq!(validator.validate("( // )\n)"), Ok(()));
+        assert_eq!(validator.validate("( /* ) "), error(2, BracketErrorKind::UnclosedRegion));
+        assert_eq!(validator.validate("(\"abc"), error(1, BracketErrorKind::UnclosedRegion));
+        assert_eq!(validator.validate("x /"), Ok(()));
+    }
+
+    #[test]
+    fn test_opener_is_bracket() {
+        let validator = BracketValidator::new().block_comment("(*", "*)");
+
+        assert_eq!(validator.validate("(a)"), Ok(()));
+        assert_eq!(validator.validate("(* ( *)"), Ok(()));
+        assert_eq!(validator.validate("(*)"), error(0, BracketErrorKind::UnclosedRegion));
+        assert_eq!(validator.validate("("), error(0, BracketErrorKind::UnclosedOpener('(')));
+    }
+
+    #[test]
+    fn test_stream() {
+        let validator = BracketValidator::new().block_comment("/*", "*/").string('"', Some('\\'));
+        let text = r#"{"a": [1, "\"]"], /* ] */ "b": {}}"#;
+
+        for size in 1..text.len() {
+            let mut stream = validator.stream();
+
+            for chunk in text.as_bytes().chunks(size) {
+                assert_eq!(stream.feed(std::str::from_utf8(chunk).unwrap()), Ok(()));
+            }
+
+            assert_eq!(stream.depth(), 0);
+            assert_eq!(stream.position(), text.len());
+            assert_eq!(stream.finish(), Ok(()));
+        }
+
+        let mut stream = validator.stream();
+        assert_eq!(stream.feed("[("), Ok(()));
+        assert_eq!(stream.depth(), 2);
+        assert_eq!(stream.feed("/"), Ok(()));
+        assert_eq!(stream.feed("*)*/ ]"), error(8, BracketErrorKind::Mismatch { expected: ')', found: ']' }));
+        assert_eq!(stream.feed(")"), error(8, BracketErrorKind::Mismatch { expected: ')', found: ']' }));
+        assert_eq!(stream.finish(), error(8, BracketErrorKind::Mismatch { expected: ')', found: ']' }));
+
+        let mut stream = validator.stream();
+        assert_eq!(stream.feed("(\"мир"), Ok(()));
+        assert_eq!(stream.finish(), error(1, BracketErrorKind::UnclosedRegion));
+    }
+
+    #[test]
+    fn test_match_brackets() {
+        assert_eq!(match_brackets(""), vec![]);
+        assert_eq!(match_brackets("([{}])"), vec![(0, 5), (1, 4), (2, 3)]);
+        assert_eq!(match_brackets("({)}"), vec![(1, 3)]);
+        assert_eq!(match_brackets("мир(да)"), vec![(3, 6)]);
+    }
+
+    #[test]
+    fn test_longest_valid_parentheses() {
+        assert_eq!(longest_valid_parentheses(""), 0);
+        assert_eq!(longest_valid_parentheses("(("), 0);
+        assert_eq!(longest_valid_parentheses("(()"), 2);
+        assert_eq!(longest_valid_parentheses("abc"), 3);
+        assert_eq!(longest_valid_parentheses("((a)(b)"), 6);
+        assert_eq!(longest_valid_parentheses("[)()]"), 2);
+    }
+
+    #[test]
+    fn test_min_insertions() {
+        assert_eq!(min_insertions(""), 0);
+        assert_eq!(min_insertions("a(b)c"), 0);
+        assert_eq!(min_insertions("))(("), 4);
+        assert_eq!(min_insertions("(]"), 2);
+        assert_eq!(min_insertions("[(])"), 2);
+    }
+
+    #[test]
+    fn test_repair() {
+        let insert = |position, bracket| BracketEdit::Insert { position, bracket };
+
+        assert_eq!(repair("(a)"), ("(a)".to_string(), vec![]));
+        assert_eq!(repair("(a"), ("(a)".to_string(), vec![insert(2, ')')]));
+        assert_eq!(repair("[(a) {"), ("[(a) ]".to_string(), vec![BracketEdit::Replace { position: 5, bracket: ']' }]));
+        assert_eq!(repair("[(a) {}"), ("[(a) {}]".to_string(), vec![insert(7, ']')]));
+        assert_eq!(repair("(["), ("()".to_string(), vec![BracketEdit::Replace { position: 1, bracket: ')' }]));
+        assert_eq!(
+            repair("{[)"),
+            ("{}".to_string(), vec![BracketEdit::Replace { position: 1, bracket: '}' }, BracketEdit::Delete { position: 2 }])
+        );
+        assert_eq!(repair(")a"), ("a".to_string(), vec![BracketEdit::Delete { position: 0 }]));
+        assert_eq!(repair("(a (b)").0, "(a (b))".to_string());
+        assert_eq!(repair("a) b)"), ("a( b)".to_string(), vec![BracketEdit::Replace { position: 1, bracket: '(' }]));
+
+        let (answer, edits) = repair("(".repeat(3001).as_str());
+        assert_eq!(edits.len(), 1501);
+        assert_eq!(is_valid_parentheses_in(answer.as_str()), true);
+        assert_eq!(min_insertions("]".repeat(3000).as_str()), 3000);
+    }
+
+    #[test]
+    fn test_repair_large() {
+        let text = "(]".to_string() + &"()".repeat(1000);
+        assert_eq!(repair(text.as_str()).1, vec![BracketEdit::Replace { position: 1, bracket: ')' }]);
+        assert_eq!(min_insertions(text.as_str()), 2);
+
+        let text = "(]".to_string() + &"(".repeat(2000);
+        let (answer, edits) = repair(text.as_str());
+        assert_eq!(is_valid_parentheses_in(answer.as_str()), true);
+        assert_eq!(edits.len(), 1001);
+        assert_eq!(min_insertions(text.as_str()), 2002);
+
+        let text = "{[(".repeat(200) + &")]}".repeat(100) + "]" + &"}".repeat(2000);
+        let (answer, _) = repair(text.as_str());
+        assert_eq!(is_valid_parentheses_in(answer.as_str()), true);
+    }
+
+    #[test]
+    fn test_reduce_keeps_cost() {
+        let mut seed: u64 = 7;
+        let mut random = |n: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % n
+        };
+
+        for _ in 0..2000 {
+            let text: String = (0..random(14)).map(|_| "()[]{}a".as_bytes()[random(7)] as char).collect();
+            let (reduced, len) = reduce(text.as_str());
+            let brackets: Vec<(usize, char)> = text.chars().enumerate().filter(|&(_, c)| c != 'a').collect();
+
+            for allow_replace in [false, true] {
+                let full = Balancer::new(brackets.clone(), len, allow_replace);
+                let cut = Balancer::new(reduced.clone(), len, allow_replace);
+                assert_eq!(full.cost[0][brackets.len()], cut.cost[0][reduced.len()], "{}", text);
+            }
+        }
+    }
+
+    /// Return every string of at most `max_len` chars of `()[]`.
+    fn bracket_strings(max_len: usize) -> Vec<String> {
+        let mut level = vec![String::new()];
+        let mut result = level.clone();
+
+        for _ in 0..max_len {
+            level = level.iter().flat_map(|s| "()[]".chars().map(move |c| format!("{}{}", s, c))).collect();
+            result.extend(level.iter().cloned());
+        }
+
+        result
+    }
+
+    /// Return the fewest insertions, deletions and replacements of brackets
+    /// that balance `s`, by breadth-first search.
+    fn fewest_edits(s: &str) -> usize {
+        let mut seen = HashSet::from([s.to_string()]);
+        let mut level = vec![s.to_string()];
+
+        for cost in 0.. {
+            if level.iter().any(|t| is_valid_parentheses_in(t.as_str())) {
+                return cost;
+            }
+
+            let mut next = vec![];
+
+            for t in &level {
+                let chars: Vec<char> = t.chars().collect();
+
+                for i in 0..=chars.len() {
+                    let mut edited = vec![];
+
+                    for b in "()[]".chars() {
+                        edited.push([&chars[..i], &[b], &chars[i..]].concat());
+
+                        if i < chars.len() {
+                            edited.push([&chars[..i], &[b], &chars[i + 1..]].concat());
+                        }
+                    }
+
+                    if i < chars.len() {
+                        edited.push([&chars[..i], &chars[i + 1..]].concat());
+                    }
+
+                    for e in edited {
+                        let e: String = e.into_iter().collect();
+
+                        if seen.insert(e.clone()) {
+                            next.push(e);
+                        }
+                    }
+                }
+            }
+
+            level = next;
+        }
+
+        unreachable!()
+    }
+
+    #[test]
+    fn test_min_insertions_is_minimal() {
+        for case in bracket_strings(6) {
+            let chars: Vec<char> = case.chars().collect();
+            let longest = (0..1u32 << chars.len())
+                .filter_map(|mask| {
+                    let kept: String = (0..chars.len()).filter(|i| mask >> i & 1 == 1).map(|i| chars[i]).collect();
+                    is_valid_parentheses_in(kept.as_str()).then_some(kept.len())
+                })
+                .max()
+                .unwrap();
+
+            assert_eq!(min_insertions(case.as_str()), chars.len() - longest, "{}", case);
+        }
+    }
+
+    #[test]
+    fn test_repair_is_minimal() {
+        let cases = ["a(b[c}d)e{", "x)y)z", "([{ a"];
+
+        for case in bracket_strings(4).iter().map(String::as_str).chain(cases) {
+            let (answer, edits) = repair(case);
+            assert_eq!(is_valid_parentheses_in(answer.as_str()), true);
+            assert_eq!(edits.len(), fewest_edits(case), "{}", case);
+        }
+    }
+
+    #[test]
+    fn test_balanced_brackets() {
+        assert_eq!(balanced_brackets(0).collect::<Vec<_>>(), vec![""]);
+        assert_eq!(balanced_brackets_with(0, &[]).collect::<Vec<_>>(), vec![""]);
+        assert_eq!(balanced_brackets_with(2, &[]).count(), 0);
+        assert_eq!(balanced_brackets(2).collect::<Vec<_>>(), vec!["(())", "()()"]);
+
+        let pairs = [('(', ')'), ('[', ']')];
+        let answer: Vec<String> = balanced_brackets_with(2, &pairs).collect();
+        assert_eq!(answer, vec!["(())", "([])", "()()", "()[]", "[()]", "[[]]", "[]()", "[][]"]);
+
+        for n in 0..7 {
+            let all: Vec<String> = balanced_brackets(n).collect();
+            assert_eq!(all.len() as u128, count_balanced(n, 1).unwrap());
+            assert!(all.windows(2).all(|w| w[0] < w[1]));
+            assert!(all.iter().all(|s| is_valid_parentheses_in(s.as_str())));
+        }
+    }
+
+    #[test]
+    fn test_rank_balanced() {
+        let pairs = [('(', ')'), ('[', ']'), ('{', '}')];
+
+        for n in 0..5 {
+            for (i, s) in balanced_brackets_with(n, &pairs).enumerate() {
+                assert_eq!(rank_balanced(&s, &pairs), Some(i as u128));
+                assert_eq!(unrank_balanced(n, i as u128, &pairs), Some(s));
+            }
+
+            assert_eq!(unrank_balanced(n, count_balanced(n, 3).unwrap(), &pairs), None);
+        }
+
+        assert_eq!(rank_balanced("(", &pairs), None);
+        assert_eq!(rank_balanced("(a)", &pairs), None);
+        assert_eq!(rank_balanced("())(", &pairs), None);
+    }
+
+    #[test]
+    fn test_rank_balanced_large() {
+        let pairs = [('(', ')')];
+        let first = "(".repeat(100) + &")".repeat(100);
+        let last = "()".repeat(100);
+
+        assert_eq!(count_balanced(100, 1), None);
+        assert_eq!(rank_balanced(&first, &pairs), None);
+        assert_eq!(rank_balanced(&last, &pairs), None);
+        assert_eq!(unrank_balanced(100, 0, &pairs), None);
+        assert_eq!(unrank_balanced(1_000_000, 0, &pairs), None);
+        assert_eq!(rank_balanced(&Rope::from("()".repeat(500_000).as_str()), &pairs), None);
+
+        let first = "(".repeat(69) + &")".repeat(69);
+        assert_eq!(rank_balanced(&first, &pairs), Some(0));
+        assert_eq!(unrank_balanced(69, 0, &pairs), Some(first));
+        assert_eq!(unrank_balanced(70, 0, &pairs), None);
+
+        let last = "()".repeat(60);
+        let answer = rank_balanced(&last, &pairs);
+        assert_eq!(answer, Some(count_balanced(60, 1).unwrap() - 1));
+        assert_eq!(unrank_balanced(60, answer.unwrap(), &pairs), Some(last));
+    }
+
+    #[test]
+    fn test_error_display() {
+        let answer = BracketValidator::new().validate("{)").unwrap_err();
+        assert_eq!(answer.to_string(), "mismatched bracket: expected `}`, found `)` at position 1");
+    }
+}
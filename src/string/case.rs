@@ -0,0 +1,250 @@
+use super::words;
+
+/// An identifier style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Case {
+    /// `snake_case`
+    Snake,
+    /// `camelCase`
+    Camel,
+    /// `PascalCase`
+    Pascal,
+    /// `kebab-case`
+    Kebab,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `Title Case`
+    Title,
+    /// `Train-Case`
+    Train,
+}
+
+/// Split a run of letters and digits at its case changes: before an
+/// uppercase letter that follows a lowercase letter or a digit, and before
+/// the last uppercase letter of an acronym that is followed by a lowercase one.
+fn split_case_changes<'a>(part: &'a str, result: &mut Vec<&'a str>) {
+    let chars: Vec<(usize, char)> = part.char_indices().collect();
+    let mut start = 0;
+
+    for (i, window) in chars.windows(2).enumerate() {
+        let ((_, prev), (pos, c)) = (window[0], window[1]);
+        let next = chars.get(i + 2).map(|&(_, next)| next);
+        let is_bound = c.is_uppercase()
+            && (prev.is_lowercase()
+                || prev.is_numeric()
+                || (prev.is_uppercase() && next.is_some_and(char::is_lowercase)));
+
+        if is_bound {
+            result.push(&part[start..pos]);
+            start = pos;
+        }
+    }
+
+    result.push(&part[start..]);
+}
+
+/// Return the words of an identifier or phrase in any of the supported
+/// styles. Words are split at word boundaries, at every char that is not a
+/// letter or a digit and at case changes; digits stay with the preceding letters.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::identifier_words;
+///
+/// let answer = identifier_words("parseHTTPServer2Config");
+/// assert_eq!(answer, vec!["parse", "HTTP", "Server2", "Config"]);
+///
+/// let answer = identifier_words("XML_http-request, v2");
+/// assert_eq!(answer, vec!["XML", "http", "request", "v2"]);
+/// ```
+pub fn identifier_words(s: &str) -> Vec<&str> {
+    let mut result = vec![];
+
+    for word in words(s) {
+        for part in word.split(|c: char| !c.is_alphanumeric()).filter(|p| !p.is_empty()) {
+            split_case_changes(part, &mut result);
+        }
+    }
+
+    result
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.as_str().to_lowercase().chars()).collect(),
+        None => String::new(),
+    }
+}
+
+/// Convert an identifier or phrase to the given style.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{to_case, Case};
+///
+/// let answer = to_case("HTTPServer", Case::Snake);
+/// assert_eq!(answer, "http_server".to_string());
+///
+/// let answer = to_case("user_id", Case::Camel);
+/// assert_eq!(answer, "userId".to_string());
+///
+/// let answer = to_case("привет мир", Case::Train);
+/// assert_eq!(answer, "Привет-Мир".to_string());
+/// ```
+pub fn to_case(s: &str, case: Case) -> String {
+    let words = identifier_words(s);
+    let lower = || words.iter().map(|w| w.to_lowercase());
+    let capitalized = || words.iter().map(|w| capitalize(w));
+
+    match case {
+        Case::Snake => lower().collect::<Vec<_>>().join("_"),
+        Case::Kebab => lower().collect::<Vec<_>>().join("-"),
+        Case::ScreamingSnake => words.iter().map(|w| w.to_uppercase()).collect::<Vec<_>>().join("_"),
+        Case::Pascal => capitalized().collect(),
+        Case::Camel => lower().take(1).chain(capitalized().skip(1)).collect(),
+        Case::Title => capitalized().collect::<Vec<_>>().join(" "),
+        Case::Train => capitalized().collect::<Vec<_>>().join("-"),
+    }
+}
+
+/// Convert to `snake_case`.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::to_snake_case;
+///
+/// let answer = to_snake_case("getHTTPResponseCode");
+/// assert_eq!(answer, "get_http_response_code".to_string());
+/// ```
+pub fn to_snake_case(s: &str) -> String {
+    to_case(s, Case::Snake)
+}
+
+/// Convert to `camelCase`.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::to_camel_case;
+///
+/// let answer = to_camel_case("XML-http-request");
+/// assert_eq!(answer, "xmlHttpRequest".to_string());
+/// ```
+pub fn to_camel_case(s: &str) -> String {
+    to_case(s, Case::Camel)
+}
+
+/// Convert to `PascalCase`.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::to_pascal_case;
+///
+/// let answer = to_pascal_case("base64_encode");
+/// assert_eq!(answer, "Base64Encode".to_string());
+/// ```
+pub fn to_pascal_case(s: &str) -> String {
+    to_case(s, Case::Pascal)
+}
+
+/// Convert to `kebab-case`.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::to_kebab_case;
+///
+/// let answer = to_kebab_case("Max Retry Count");
+/// assert_eq!(answer, "max-retry-count".to_string());
+/// ```
+pub fn to_kebab_case(s: &str) -> String {
+    to_case(s, Case::Kebab)
+}
+
+/// Convert to `SCREAMING_SNAKE_CASE`.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::to_screaming_snake_case;
+///
+/// let answer = to_screaming_snake_case("maxRetryCount");
+/// assert_eq!(answer, "MAX_RETRY_COUNT".to_string());
+/// ```
+pub fn to_screaming_snake_case(s: &str) -> String {
+    to_case(s, Case::ScreamingSnake)
+}
+
+/// Convert to `Title Case`.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::to_title_case;
+///
+/// let answer = to_title_case("the_lord_of_the_rings");
+/// assert_eq!(answer, "The Lord Of The Rings".to_string());
+/// ```
+pub fn to_title_case(s: &str) -> String {
+    to_case(s, Case::Title)
+}
+
+/// Convert to `Train-Case`.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::to_train_case;
+///
+/// let answer = to_train_case("contentType");
+/// assert_eq!(answer, "Content-Type".to_string());
+/// ```
+pub fn to_train_case(s: &str) -> String {
+    to_case(s, Case::Train)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifier_words() {
+        assert_eq!(identifier_words(""), Vec::<&str>::new());
+        assert_eq!(identifier_words("__init__"), vec!["init"]);
+        assert_eq!(identifier_words("ABC"), vec!["ABC"]);
+        assert_eq!(identifier_words("IOError"), vec!["IO", "Error"]);
+        assert_eq!(identifier_words("utf8ToUTF16"), vec!["utf8", "To", "UTF16"]);
+        assert_eq!(identifier_words("значениеПоУмолчанию"), vec!["значение", "По", "Умолчанию"]);
+    }
+
+    #[test]
+    fn test_to_case() {
+        let cases = [
+            (Case::Snake, "http_server_go"),
+            (Case::Camel, "httpServerGo"),
+            (Case::Pascal, "HttpServerGo"),
+            (Case::Kebab, "http-server-go"),
+            (Case::ScreamingSnake, "HTTP_SERVER_GO"),
+            (Case::Title, "Http Server Go"),
+            (Case::Train, "Http-Server-Go"),
+        ];
+
+        for (case, expected) in cases {
+            assert_eq!(to_case("HTTPServer go", case), expected.to_string());
+
+            for (other, _) in cases {
+                assert_eq!(to_case(&to_case(expected, other), case), expected.to_string());
+            }
+        }
+
+        assert_eq!(to_snake_case("Version 2 beta"), "version_2_beta".to_string());
+        assert_eq!(to_camel_case("version 2 beta"), "version2Beta".to_string());
+        assert_eq!(to_snake_case("ΣΟΦΙΑ ΟΔΟΣ"), "σοφια_οδο\u{3c2}".to_string());
+    }
+}
@@ -0,0 +1,466 @@
+use std::fmt;
+
+/// One step of an edit script that turns the old sequence into the new one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffOp<T> {
+    /// The item is present in both sequences.
+    Equal(T),
+    /// The item is only present in the old sequence.
+    Delete(T),
+    /// The item is only present in the new sequence.
+    Insert(T),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Return the shortest edit script as `(kind, old index, new index)`, where
+/// the index that does not apply to the kind points at the next item.
+fn myers<T: PartialEq>(old: &[T], new: &[T]) -> Vec<(Kind, usize, usize)> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let offset = n + m + 1;
+    let mut v = vec![0_isize; 2 * offset as usize + 1];
+    let at = |k: isize| (k + offset) as usize;
+    // `trace[d]` holds `v[-d..=d]` as it was after step `d`.
+    let mut trace: Vec<Vec<isize>> = vec![];
+
+    'search: for d in 0..=n + m {
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[at(k - 1)] < v[at(k + 1)]) {
+                v[at(k + 1)]
+            } else {
+                v[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+
+            v[at(k)] = x;
+
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+
+        trace.push(v[at(-d)..=at(d)].to_vec());
+    }
+
+    let mut script = vec![];
+    let (mut x, mut y) = (n, m);
+
+    for d in (0..=trace.len() as isize).rev() {
+        let k = x - y;
+        // The furthest x on diagonal `k` after step `d - 1`.
+        let prev = |k: isize| trace[d as usize - 1][(k + d - 1) as usize];
+        let (prev_x, prev_y) = if d == 0 {
+            (0, 0)
+        } else {
+            let prev_k = if k == -d || (k != d && prev(k - 1) < prev(k + 1)) { k + 1 } else { k - 1 };
+            (prev(prev_k), prev(prev_k) - prev_k)
+        };
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            script.push((Kind::Equal, x as usize, y as usize));
+        }
+
+        if d > 0 {
+            if x == prev_x {
+                script.push((Kind::Insert, x as usize, prev_y as usize));
+            } else {
+                script.push((Kind::Delete, prev_x as usize, y as usize));
+            }
+        }
+
+        x = prev_x;
+        y = prev_y;
+    }
+
+    script.reverse();
+    script
+}
+
+/// Return the shortest edit script between two slices, computed with
+/// Myers' `O(ND)` algorithm.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{diff, DiffOp};
+///
+/// let answer = diff(&[1, 2, 3], &[1, 3, 4]);
+/// assert_eq!(answer, vec![DiffOp::Equal(&1), DiffOp::Delete(&2), DiffOp::Equal(&3), DiffOp::Insert(&4)]);
+/// ```
+pub fn diff<'a, T: PartialEq>(old: &'a [T], new: &'a [T]) -> Vec<DiffOp<&'a T>> {
+    myers(old, new)
+        .into_iter()
+        .map(|(kind, i, j)| match kind {
+            Kind::Equal => DiffOp::Equal(&old[i]),
+            Kind::Delete => DiffOp::Delete(&old[i]),
+            Kind::Insert => DiffOp::Insert(&new[j]),
+        })
+        .collect()
+}
+
+/// Return the line-level edit script. Lines keep their `\n` terminator.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{diff_lines, DiffOp};
+///
+/// let answer = diff_lines("a\nb\nc\n", "a\nc\nd\n");
+/// assert_eq!(answer, vec![
+///     DiffOp::Equal("a\n"),
+///     DiffOp::Delete("b\n"),
+///     DiffOp::Equal("c\n"),
+///     DiffOp::Insert("d\n"),
+/// ]);
+/// ```
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffOp<&'a str>> {
+    let old: Vec<&str> = old.split_inclusive('\n').collect();
+    let new: Vec<&str> = new.split_inclusive('\n').collect();
+
+    diff(&old, &new)
+        .into_iter()
+        .map(|op| match op {
+            DiffOp::Equal(s) => DiffOp::Equal(*s),
+            DiffOp::Delete(s) => DiffOp::Delete(*s),
+            DiffOp::Insert(s) => DiffOp::Insert(*s),
+        })
+        .collect()
+}
+
+/// Return the char-level edit script.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{diff_chars, DiffOp};
+///
+/// let answer = diff_chars("кот", "кит");
+/// assert_eq!(answer, vec![
+///     DiffOp::Equal('к'),
+///     DiffOp::Delete('о'),
+///     DiffOp::Insert('и'),
+///     DiffOp::Equal('т'),
+/// ]);
+/// ```
+pub fn diff_chars(old: &str, new: &str) -> Vec<DiffOp<char>> {
+    let old: Vec<char> = old.chars().collect();
+    let new: Vec<char> = new.chars().collect();
+
+    diff(&old, &new)
+        .into_iter()
+        .map(|op| match op {
+            DiffOp::Equal(&c) => DiffOp::Equal(c),
+            DiffOp::Delete(&c) => DiffOp::Delete(c),
+            DiffOp::Insert(&c) => DiffOp::Insert(c),
+        })
+        .collect()
+}
+
+const NO_NEWLINE: &str = "\\ No newline at end of file";
+
+fn push_line(result: &mut String, prefix: char, line: &str) {
+    result.push(prefix);
+    result.push_str(line);
+
+    if !line.ends_with('\n') {
+        result.push('\n');
+        result.push_str(NO_NEWLINE);
+        result.push('\n');
+    }
+}
+
+fn hunk_range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, count),
+    }
+}
+
+/// Return the line diff in unified format with the given number of context
+/// lines, i.e. the hunks that `diff -U<context>` prints after the file headers.
+/// Identical inputs give an empty string.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::unified_diff;
+///
+/// let answer = unified_diff("a\nb\nc\nd\n", "a\nB\nc\nd\n", 1);
+/// assert_eq!(answer, "@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n");
+/// ```
+pub fn unified_diff(old: &str, new: &str, context: usize) -> String {
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+    let script = myers(&old_lines, &new_lines);
+    let changes: Vec<usize> = (0..script.len()).filter(|&i| script[i].0 != Kind::Equal).collect();
+
+    let mut hunks: Vec<(usize, usize)> = vec![];
+    for &i in &changes {
+        let (from, to) = (i.saturating_sub(context), (i + 1 + context).min(script.len()));
+
+        match hunks.last_mut() {
+            Some(last) if from <= last.1 => last.1 = to,
+            _ => hunks.push((from, to)),
+        }
+    }
+
+    let mut result = String::new();
+
+    for (from, to) in hunks {
+        let ops = &script[from..to];
+        let old_count = ops.iter().filter(|op| op.0 != Kind::Insert).count();
+        let new_count = ops.iter().filter(|op| op.0 != Kind::Delete).count();
+        let (_, old_start, new_start) = ops[0];
+
+        result.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_count),
+            hunk_range(new_start, new_count)
+        ));
+
+        for &(kind, i, j) in ops {
+            match kind {
+                Kind::Equal => push_line(&mut result, ' ', old_lines[i]),
+                Kind::Delete => push_line(&mut result, '-', old_lines[i]),
+                Kind::Insert => push_line(&mut result, '+', new_lines[j]),
+            }
+        }
+    }
+
+    result
+}
+
+/// The reason a patch could not be applied by [`apply_patch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchErrorKind {
+    /// A line that is neither a hunk header nor a hunk line.
+    MalformedLine,
+    /// A hunk header that cannot be parsed.
+    MalformedHeader,
+    /// A hunk whose line counts disagree with its header.
+    MalformedHunk,
+    /// A context or deleted line that does not match the source.
+    Mismatch,
+    /// A hunk that starts before the end of the previous one or past the source.
+    OutOfOrder,
+}
+
+/// An error produced while applying a patch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatchError {
+    /// 1-based line of the patch where the problem was found.
+    pub line: usize,
+    pub kind: PatchErrorKind,
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self.kind {
+            PatchErrorKind::MalformedLine => "malformed line",
+            PatchErrorKind::MalformedHeader => "malformed hunk header",
+            PatchErrorKind::MalformedHunk => "hunk does not match its header",
+            PatchErrorKind::Mismatch => "patch does not match the source",
+            PatchErrorKind::OutOfOrder => "hunk is out of order",
+        };
+
+        write!(f, "{} at line {}", message, self.line)
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+fn parse_range(s: &str) -> Option<(usize, usize)> {
+    match s.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((s.parse().ok()?, 1)),
+    }
+}
+
+fn parse_header(line: &str) -> Option<((usize, usize), (usize, usize))> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (old, rest) = rest.split_once(" +")?;
+    let (new, _) = rest.split_once(" @@")?;
+
+    Some((parse_range(old)?, parse_range(new)?))
+}
+
+/// Apply a unified diff, as produced by [`unified_diff`], to the source.
+/// File headers starting with `---` or `+++` are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{apply_patch, unified_diff, PatchErrorKind};
+///
+/// let old = "3#kek3#lol\n0#\n";
+/// let new = "3#kek\n0#\n3#pip";
+/// let patch = unified_diff(old, new, 3);
+/// assert_eq!(apply_patch(old, &patch), Ok(new.to_string()));
+///
+/// let answer = apply_patch("other\n", &patch).unwrap_err();
+/// assert_eq!(answer.kind, PatchErrorKind::Mismatch);
+/// ```
+pub fn apply_patch(source: &str, patch: &str) -> Result<String, PatchError> {
+    let source: Vec<&str> = source.split_inclusive('\n').collect();
+    // Only `\n` ends a line, so a `\r` of CRLF text stays part of it.
+    let lines: Vec<&str> = patch.split_terminator('\n').collect();
+    let mut result = String::new();
+    let mut position = 0;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let error = move |kind| PatchError { line: i + 1, kind };
+
+        if lines[i].starts_with("---") || lines[i].starts_with("+++") {
+            i += 1;
+            continue;
+        }
+
+        if !lines[i].starts_with("@@") {
+            return Err(error(PatchErrorKind::MalformedLine));
+        }
+
+        let ((old_start, old_count), (_, new_count)) =
+            parse_header(lines[i]).ok_or(error(PatchErrorKind::MalformedHeader))?;
+        let start = if old_count == 0 { old_start } else { old_start.saturating_sub(1) };
+
+        if start < position || start > source.len() {
+            return Err(error(PatchErrorKind::OutOfOrder));
+        }
+
+        source[position..start].iter().for_each(|line| result.push_str(line));
+        position = start;
+        i += 1;
+
+        let (mut old_seen, mut new_seen) = (0, 0);
+
+        while i < lines.len() && !lines[i].starts_with("@@") && (old_seen < old_count || new_seen < new_count) {
+            let error = move |kind| PatchError { line: i + 1, kind };
+            let (tag, text) = lines[i].split_at(lines[i].len().min(1));
+            let has_newline = lines.get(i + 1) != Some(&NO_NEWLINE);
+            let line = if has_newline { format!("{}\n", text) } else { text.to_string() };
+
+            match tag {
+                " " | "-" => {
+                    if source.get(position) != Some(&line.as_str()) {
+                        return Err(error(PatchErrorKind::Mismatch));
+                    }
+
+                    position += 1;
+                    old_seen += 1;
+
+                    if tag == " " {
+                        result.push_str(&line);
+                        new_seen += 1;
+                    }
+                }
+                "+" => {
+                    result.push_str(&line);
+                    new_seen += 1;
+                }
+                _ => return Err(error(PatchErrorKind::MalformedLine)),
+            }
+
+            i += if has_newline { 1 } else { 2 };
+        }
+
+        if old_seen != old_count || new_seen != new_count {
+            return Err(error(PatchErrorKind::MalformedHunk));
+        }
+    }
+
+    source[position..].iter().for_each(|line| result.push_str(line));
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let answer = diff::<u8>(&[], &[]);
+        assert_eq!(answer, vec![]);
+
+        let answer = diff(&['a', 'b'], &[]);
+        assert_eq!(answer, vec![DiffOp::Delete(&'a'), DiffOp::Delete(&'b')]);
+
+        let answer = diff_chars("abcabba", "cbabac");
+        let changes = answer.iter().filter(|op| !matches!(op, DiffOp::Equal(_))).count();
+        assert_eq!(changes, 5);
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let new = "1\n2\nthree\n4\n5\n6\n7\n8\nnine\n";
+
+        let answer = unified_diff(old, new, 1);
+        assert_eq!(answer, "@@ -2,3 +2,3 @@\n 2\n-3\n+three\n 4\n@@ -8,2 +8,2 @@\n 8\n-9\n+nine\n");
+
+        let answer = unified_diff(old, new, 3);
+        assert_eq!(answer.matches("@@ -").count(), 1);
+
+        let answer = unified_diff("a\n", "a\n", 3);
+        assert_eq!(answer, "");
+
+        let answer = unified_diff("", "x", 0);
+        assert_eq!(answer, "@@ -0,0 +1 @@\n+x\n\\ No newline at end of file\n");
+    }
+
+    #[test]
+    fn test_apply_patch() {
+        let cases = [
+            ("", "a\nb\n"),
+            ("a\nb\n", ""),
+            ("a\nb\nc", "a\nc\nd"),
+            ("x\ny\nz\n", "x\ny\nz"),
+            ("1\n2\n3\n4\n5\n6\n7\n8\n", "0\n1\n2\n3\n5\n6\n7\n8\n9\n"),
+            ("a\r\nb\r\nc\r\n", "a\r\nB\r\nc\r\n"),
+            ("a\r\nb", "a\nb\r\n"),
+        ];
+
+        for (old, new) in cases {
+            for context in 0..3 {
+                let patch = unified_diff(old, new, context);
+                assert_eq!(apply_patch(old, &patch), Ok(new.to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_diff_long() {
+        let old: Vec<u32> = (0..2000).collect();
+        let new: Vec<u32> = (0..2000).filter(|i| i % 7 != 0).chain(5000..5100).collect();
+        let answer = diff(&old, &new);
+
+        let deleted = answer.iter().filter(|op| matches!(op, DiffOp::Delete(_))).count();
+        let inserted = answer.iter().filter(|op| matches!(op, DiffOp::Insert(_))).count();
+        assert_eq!((deleted, inserted), (286, 100));
+    }
+
+    #[test]
+    fn test_apply_patch_errors() {
+        let answer = apply_patch("a\n", "--- a\n+++ b\n@@ -1 +1 @@\n-b\n+c\n").unwrap_err();
+        assert_eq!(answer, PatchError { line: 4, kind: PatchErrorKind::Mismatch });
+
+        let answer = apply_patch("a\n", "@@ -x +1 @@\n").unwrap_err();
+        assert_eq!(answer, PatchError { line: 1, kind: PatchErrorKind::MalformedHeader });
+
+        let answer = apply_patch("a\n", "@@ -1 +1,2 @@\n a\n").unwrap_err();
+        assert_eq!(answer.kind, PatchErrorKind::MalformedHunk);
+    }
+}
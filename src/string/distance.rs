@@ -0,0 +1,484 @@
+/// One step of an alignment between two strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlignOp {
+    /// The char is the same in both strings.
+    Match(char),
+    /// The first char is replaced by the second.
+    Substitute(char, char),
+    /// The char is only present in the second string.
+    Insert(char),
+    /// The char is only present in the first string.
+    Delete(char),
+    /// Two adjacent chars `(x, y)` of the first string appear as `y, x` in the second.
+    Transpose(char, char),
+}
+
+/// Full dynamic programming table, with optimal string alignment
+/// transpositions when `transpositions` is set.
+fn edit_table(a: &[char], b: &[char], transpositions: bool) -> Vec<Vec<usize>> {
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+
+            if transpositions && i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d
+}
+
+fn align(a: &[char], b: &[char], transpositions: bool) -> (usize, Vec<AlignOp>) {
+    let d = edit_table(a, b, transpositions);
+    let (mut i, mut j) = (a.len(), b.len());
+    let mut ops = vec![];
+
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && a[i - 1] == b[j - 1] && d[i][j] == d[i - 1][j - 1] {
+            ops.push(AlignOp::Match(a[i - 1]));
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && j > 0 && d[i][j] == d[i - 1][j - 1] + 1 {
+            ops.push(AlignOp::Substitute(a[i - 1], b[j - 1]));
+            i -= 1;
+            j -= 1;
+        } else if transpositions
+            && i > 1
+            && j > 1
+            && a[i - 1] == b[j - 2]
+            && a[i - 2] == b[j - 1]
+            && d[i][j] == d[i - 2][j - 2] + 1
+        {
+            ops.push(AlignOp::Transpose(a[i - 2], a[i - 1]));
+            i -= 2;
+            j -= 2;
+        } else if i > 0 && d[i][j] == d[i - 1][j] + 1 {
+            ops.push(AlignOp::Delete(a[i - 1]));
+            i -= 1;
+        } else {
+            ops.push(AlignOp::Insert(b[j - 1]));
+            j -= 1;
+        }
+    }
+
+    ops.reverse();
+    (d[a.len()][b.len()], ops)
+}
+
+/// Return the Levenshtein distance, i.e. the minimum number of single char
+/// insertions, deletions and substitutions.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::levenshtein;
+///
+/// let answer = levenshtein("kitten", "sitting");
+/// assert_eq!(answer, 3);
+///
+/// let answer = levenshtein("ёлка", "елка");
+/// assert_eq!(answer, 1);
+/// ```
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    levenshtein_bounded(a, b, usize::MAX).unwrap()
+}
+
+/// Return the Levenshtein distance if it is at most `max`, otherwise `None`.
+/// The computation stops as soon as the bound is known to be exceeded, and
+/// only fills cells within `max` of the diagonal.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::levenshtein_bounded;
+///
+/// let answer = levenshtein_bounded("kitten", "sitting", 3);
+/// assert_eq!(answer, Some(3));
+///
+/// let answer = levenshtein_bounded("kitten", "sitting", 2);
+/// assert_eq!(answer, None);
+/// ```
+pub fn levenshtein_bounded(a: &str, b: &str, max: usize) -> Option<usize> {
+    let (a, b) = shorter_last(a, b);
+    let (n, m) = (a.len(), b.len());
+
+    if n - m > max {
+        return None;
+    }
+
+    // Cells off the diagonal by more than `max` can't lead back under the
+    // bound, so only a band of `2 * max + 1` cells is filled per row, and
+    // any value above `max` is stored as `limit`. The distance is at most
+    // `n`, so a larger bound is no tighter than `n`.
+    let max = max.min(n);
+    let limit = max + 1;
+    let mut prev: Vec<usize> = (0..=m).map(|j| j.min(limit)).collect();
+    let mut current = vec![limit; m + 1];
+
+    for i in 1..=n {
+        let low = i.saturating_sub(max).max(1);
+        let high = m.min(i + max);
+
+        current[low - 1] = if low == 1 { i.min(limit) } else { limit };
+        let mut row_min = current[low - 1];
+
+        for j in low..=high {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (prev[j] + 1).min(current[j - 1] + 1).min(prev[j - 1] + cost).min(limit);
+            row_min = row_min.min(current[j]);
+        }
+
+        if high < m {
+            current[high + 1] = limit;
+        }
+
+        if row_min == limit {
+            return None;
+        }
+
+        std::mem::swap(&mut prev, &mut current);
+    }
+
+    Some(prev[m]).filter(|&d| d <= max)
+}
+
+/// Collect the chars of both strings, the longer one first, so that a row of
+/// the table spans the shorter one.
+fn shorter_last(a: &str, b: &str) -> (Vec<char>, Vec<char>) {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.len() < b.len() {
+        (b, a)
+    } else {
+        (a, b)
+    }
+}
+
+/// Return the Levenshtein distance together with an optimal alignment.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{levenshtein_alignment, AlignOp};
+///
+/// let (distance, ops) = levenshtein_alignment("cat", "cut");
+/// assert_eq!(distance, 1);
+/// assert_eq!(ops, vec![AlignOp::Match('c'), AlignOp::Substitute('a', 'u'), AlignOp::Match('t')]);
+/// ```
+pub fn levenshtein_alignment(a: &str, b: &str) -> (usize, Vec<AlignOp>) {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    align(&a, &b, false)
+}
+
+/// Return the optimal string alignment distance, i.e. Levenshtein distance
+/// where swapping two adjacent chars also counts as one edit. No substring
+/// is edited more than once.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::damerau_levenshtein;
+///
+/// let answer = damerau_levenshtein("ca", "ac");
+/// assert_eq!(answer, 1);
+///
+/// let answer = damerau_levenshtein("ca", "abc");
+/// assert_eq!(answer, 3);
+/// ```
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let (a, b) = shorter_last(a, b);
+
+    // A transposition looks two rows back, so three rows of the table are kept.
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (prev[j] + 1).min(current[j - 1] + 1).min(prev[j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+
+        std::mem::swap(&mut before, &mut prev);
+        std::mem::swap(&mut prev, &mut current);
+    }
+
+    prev[b.len()]
+}
+
+/// Return the optimal string alignment distance together with an optimal alignment.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{damerau_levenshtein_alignment, AlignOp};
+///
+/// let (distance, ops) = damerau_levenshtein_alignment("form", "from");
+/// assert_eq!(distance, 1);
+/// assert_eq!(ops, vec![
+///     AlignOp::Match('f'),
+///     AlignOp::Transpose('o', 'r'),
+///     AlignOp::Match('m'),
+/// ]);
+/// ```
+pub fn damerau_levenshtein_alignment(a: &str, b: &str) -> (usize, Vec<AlignOp>) {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    align(&a, &b, true)
+}
+
+/// Return the number of positions with different chars, or `None` if the
+/// strings have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::hamming;
+///
+/// let answer = hamming("karolin", "kathrin");
+/// assert_eq!(answer, Some(3));
+///
+/// let answer = hamming("kek", "keks");
+/// assert_eq!(answer, None);
+/// ```
+pub fn hamming(a: &str, b: &str) -> Option<usize> {
+    let (mut a, mut b) = (a.chars(), b.chars());
+    let mut distance = 0;
+
+    loop {
+        match (a.next(), b.next()) {
+            (Some(x), Some(y)) => distance += usize::from(x != y),
+            (None, None) => return Some(distance),
+            _ => return None,
+        }
+    }
+}
+
+/// Return the Jaro similarity, from `0.0` for no similarity to `1.0` for equal strings.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::jaro;
+///
+/// let answer = jaro("martha", "marhta");
+/// assert!((answer - 0.944).abs() < 0.001);
+///
+/// let answer = jaro("abc", "xyz");
+/// assert_eq!(answer, 0.0);
+/// ```
+pub fn jaro(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0;
+
+    for (i, &c) in a.iter().enumerate() {
+        let (from, to) = (i.saturating_sub(window), (i + window + 1).min(b.len()));
+
+        if let Some(j) = (from..to).find(|&j| !b_matched[j] && b[j] == c) {
+            a_matched[i] = true;
+            b_matched[j] = true;
+            matches += 1;
+        }
+    }
+
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let a_seq = a.iter().zip(&a_matched).filter(|(_, &m)| m).map(|(c, _)| c);
+    let b_seq = b.iter().zip(&b_matched).filter(|(_, &m)| m).map(|(c, _)| c);
+    let transpositions = a_seq.zip(b_seq).filter(|(x, y)| x != y).count() / 2;
+    let m = matches as f64;
+
+    (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0
+}
+
+/// Return the Jaro-Winkler similarity, which boosts the Jaro similarity of
+/// strings sharing a common prefix of up to 4 chars, with the usual scaling
+/// factor of `0.1`.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::jaro_winkler;
+///
+/// let answer = jaro_winkler("martha", "marhta");
+/// assert!((answer - 0.961).abs() < 0.001);
+///
+/// let answer = jaro_winkler("dixon", "dicksonx");
+/// assert!((answer - 0.813).abs() < 0.001);
+/// ```
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let similarity = jaro(a, b);
+    let prefix = a.chars().zip(b.chars()).take(4).take_while(|(x, y)| x == y).count();
+
+    similarity + prefix as f64 * 0.1 * (1.0 - similarity)
+}
+
+fn normalize(distance: usize, a: &str, b: &str) -> f64 {
+    let len = a.chars().count().max(b.chars().count());
+
+    if len == 0 {
+        1.0
+    } else {
+        1.0 - distance as f64 / len as f64
+    }
+}
+
+/// Return the Levenshtein similarity scaled to `0.0..=1.0` by the length of
+/// the longer string, where `1.0` means equal strings.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::normalized_levenshtein;
+///
+/// let answer = normalized_levenshtein("kek", "lol");
+/// assert_eq!(answer, 0.0);
+///
+/// let answer = normalized_levenshtein("kek", "keks");
+/// assert_eq!(answer, 0.75);
+/// ```
+pub fn normalized_levenshtein(a: &str, b: &str) -> f64 {
+    normalize(levenshtein(a, b), a, b)
+}
+
+/// Return the optimal string alignment similarity scaled to `0.0..=1.0` by
+/// the length of the longer string, where `1.0` means equal strings.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::normalized_damerau_levenshtein;
+///
+/// let answer = normalized_damerau_levenshtein("abcd", "abdc");
+/// assert_eq!(answer, 0.75);
+/// ```
+pub fn normalized_damerau_levenshtein(a: &str, b: &str) -> f64 {
+    normalize(damerau_levenshtein(a, b), a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        let answer = levenshtein("", "abc");
+        assert_eq!(answer, 3);
+
+        let answer = levenshtein("flaw", "lawn");
+        assert_eq!(answer, 2);
+
+        let answer = levenshtein_bounded("abc", "abcdef", 2);
+        assert_eq!(answer, None);
+
+        let answer = levenshtein_bounded("same", "same", 0);
+        assert_eq!(answer, Some(0));
+    }
+
+    #[test]
+    fn test_alignment() {
+        let (distance, ops) = levenshtein_alignment("ab", "ba");
+        assert_eq!(distance, 2);
+        assert_eq!(ops, vec![AlignOp::Substitute('a', 'b'), AlignOp::Substitute('b', 'a')]);
+
+        let (distance, ops) = levenshtein_alignment("", "ы");
+        assert_eq!(distance, 1);
+        assert_eq!(ops, vec![AlignOp::Insert('ы')]);
+
+        let (distance, ops) = damerau_levenshtein_alignment("abc", "ac");
+        assert_eq!(distance, 1);
+        assert_eq!(ops, vec![AlignOp::Match('a'), AlignOp::Delete('b'), AlignOp::Match('c')]);
+    }
+
+    #[test]
+    fn test_damerau_levenshtein() {
+        let answer = damerau_levenshtein("abcdef", "abdcfe");
+        assert_eq!(answer, 2);
+
+        let answer = damerau_levenshtein("привет", "пирвет");
+        assert_eq!(answer, 1);
+
+        let answer = damerau_levenshtein("ab", "bca");
+        assert_eq!(answer, 3);
+    }
+
+    #[test]
+    fn test_rows_match_table() {
+        let mut seed: u64 = 11;
+        let mut random = |n: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % n
+        };
+
+        for _ in 0..2000 {
+            let a: Vec<char> = (0..random(9)).map(|_| "abc".as_bytes()[random(3)] as char).collect();
+            let b: Vec<char> = (0..random(9)).map(|_| "abc".as_bytes()[random(3)] as char).collect();
+            let (a_str, b_str): (String, String) = (a.iter().collect(), b.iter().collect());
+
+            let distance = edit_table(&a, &b, false)[a.len()][b.len()];
+            for max in 0..6 {
+                let answer = levenshtein_bounded(&a_str, &b_str, max);
+                assert_eq!(answer, Some(distance).filter(|&d| d <= max), "{} {} {}", a_str, b_str, max);
+            }
+
+            let answer = damerau_levenshtein(&a_str, &b_str);
+            assert_eq!(answer, edit_table(&a, &b, true)[a.len()][b.len()], "{} {}", a_str, b_str);
+        }
+    }
+
+    #[test]
+    fn test_jaro_winkler() {
+        let answer = jaro("", "");
+        assert_eq!(answer, 1.0);
+
+        let answer = jaro("a", "");
+        assert_eq!(answer, 0.0);
+
+        let answer = jaro_winkler("dwayne", "duane");
+        assert!((answer - 0.84).abs() < 0.001);
+
+        let answer = jaro_winkler("same", "same");
+        assert_eq!(answer, 1.0);
+    }
+
+    #[test]
+    fn test_hamming() {
+        let answer = hamming("", "");
+        assert_eq!(answer, Some(0));
+
+        let answer = hamming("ёжик", "ёжек");
+        assert_eq!(answer, Some(1));
+    }
+}
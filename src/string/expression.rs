@@ -0,0 +1,709 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// Deepest nesting of parentheses, operators and calls that is accepted, so
+/// that parsing can't overflow the stack.
+const MAX_DEPTH: usize = 256;
+
+/// The reason an expression was rejected by [`Expr::parse`] or
+/// [`Expr::eval`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpressionErrorKind {
+    /// A char that can't start a token, e.g. `#`.
+    UnexpectedChar,
+    /// A number that can't be read, e.g. `1.2.3`.
+    InvalidNumber,
+    /// A token where it doesn't belong, e.g. the second number of `1 2`.
+    UnexpectedToken,
+    /// The expression ended where an operand was expected.
+    UnexpectedEnd,
+    /// A `(` without the matching `)`.
+    UnclosedParenthesis,
+    /// A `)` without the matching `(`.
+    UnopenedParenthesis,
+    /// A variable that is not in the environment.
+    UnknownVariable,
+    /// A function that is not in the environment.
+    UnknownFunction,
+    /// A call with a number of arguments the function doesn't take.
+    WrongArgumentCount,
+    /// An expression nested more than 256 levels deep, counting
+    /// parentheses, unary operators, right operands of `^` and calls. A
+    /// chain of left-associative operators like `1 + 1 + ...` doesn't nest.
+    TooDeep,
+}
+
+/// An error produced while parsing or evaluating an expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpressionError {
+    /// Char index in the expression where the problem was found.
+    pub position: usize,
+    pub kind: ExpressionErrorKind,
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self.kind {
+            ExpressionErrorKind::UnexpectedChar => "unexpected character",
+            ExpressionErrorKind::InvalidNumber => "invalid number",
+            ExpressionErrorKind::UnexpectedToken => "unexpected token",
+            ExpressionErrorKind::UnexpectedEnd => "unexpected end of expression",
+            ExpressionErrorKind::UnclosedParenthesis => "unclosed parenthesis",
+            ExpressionErrorKind::UnopenedParenthesis => "unopened parenthesis",
+            ExpressionErrorKind::UnknownVariable => "unknown variable",
+            ExpressionErrorKind::UnknownFunction => "unknown function",
+            ExpressionErrorKind::WrongArgumentCount => "wrong number of arguments",
+            ExpressionErrorKind::TooDeep => "expression is nested too deeply",
+        };
+
+        write!(f, "{} at position {}", message, self.position)
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+/// A binary operator of an [`Expr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+}
+
+impl BinaryOp {
+    fn from_char(c: char) -> Option<BinaryOp> {
+        let op = match c {
+            '+' => BinaryOp::Add,
+            '-' => BinaryOp::Sub,
+            '*' => BinaryOp::Mul,
+            '/' => BinaryOp::Div,
+            '%' => BinaryOp::Rem,
+            '^' => BinaryOp::Pow,
+            _ => return None,
+        };
+
+        Some(op)
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            BinaryOp::Add => '+',
+            BinaryOp::Sub => '-',
+            BinaryOp::Mul => '*',
+            BinaryOp::Div => '/',
+            BinaryOp::Rem => '%',
+            BinaryOp::Pow => '^',
+        }
+    }
+
+    /// Return the left and right binding powers. `^` is right-associative
+    /// and binds tighter than unary minus, so `-2^2` is `-(2^2)`.
+    fn binding_power(self) -> (u8, u8) {
+        match self {
+            BinaryOp::Add | BinaryOp::Sub => (1, 2),
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => (3, 4),
+            BinaryOp::Pow => (6, 5),
+        }
+    }
+
+    fn apply(self, left: f64, right: f64) -> f64 {
+        match self {
+            BinaryOp::Add => left + right,
+            BinaryOp::Sub => left - right,
+            BinaryOp::Mul => left * right,
+            BinaryOp::Div => left / right,
+            BinaryOp::Rem => left % right,
+            BinaryOp::Pow => left.powf(right),
+        }
+    }
+}
+
+/// The binding power of unary `-` and `+`.
+const UNARY_POWER: u8 = 5;
+
+/// A function of an [`Environment`].
+#[derive(Debug, Clone, Copy)]
+struct Function {
+    arity: Option<usize>,
+    body: fn(&[f64]) -> f64,
+}
+
+/// Variables and functions that an [`Expr`] is evaluated with.
+///
+/// The default environment has the constants `pi` and `e`, the functions
+/// `abs`, `ceil`, `cos`, `exp`, `floor`, `ln`, `log10`, `round`, `sin`,
+/// `sqrt` and `tan` of one argument, `pow` of two, and `min` and `max` of
+/// any number of arguments.
+#[derive(Debug, Clone)]
+pub struct Environment {
+    variables: HashMap<String, f64>,
+    functions: HashMap<String, Function>,
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        let mut environment = Environment { variables: HashMap::new(), functions: HashMap::new() };
+
+        environment.insert_variable("pi", std::f64::consts::PI);
+        environment.insert_variable("e", std::f64::consts::E);
+
+        environment.insert_function("abs", Some(1), |args| args[0].abs());
+        environment.insert_function("ceil", Some(1), |args| args[0].ceil());
+        environment.insert_function("cos", Some(1), |args| args[0].cos());
+        environment.insert_function("exp", Some(1), |args| args[0].exp());
+        environment.insert_function("floor", Some(1), |args| args[0].floor());
+        environment.insert_function("ln", Some(1), |args| args[0].ln());
+        environment.insert_function("log10", Some(1), |args| args[0].log10());
+        environment.insert_function("round", Some(1), |args| args[0].round());
+        environment.insert_function("sin", Some(1), |args| args[0].sin());
+        environment.insert_function("sqrt", Some(1), |args| args[0].sqrt());
+        environment.insert_function("tan", Some(1), |args| args[0].tan());
+        environment.insert_function("pow", Some(2), |args| args[0].powf(args[1]));
+        environment.insert_function("min", None, |args| args.iter().copied().fold(f64::INFINITY, f64::min));
+        environment.insert_function("max", None, |args| args.iter().copied().fold(f64::NEG_INFINITY, f64::max));
+
+        environment
+    }
+}
+
+impl Environment {
+    /// Create an environment with the default constants and functions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a variable and return its previous value.
+    pub fn insert_variable(&mut self, name: &str, value: f64) -> Option<f64> {
+        self.variables.insert(name.to_string(), value)
+    }
+
+    /// Set a function of `arity` arguments, or of any number of arguments if
+    /// `arity` is `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::{Environment, Expr};
+    ///
+    /// let mut environment = Environment::new();
+    /// environment.insert_function("avg", None, |args| args.iter().sum::<f64>() / args.len() as f64);
+    ///
+    /// let answer = Expr::parse("avg(1, 2, 6)").unwrap().eval(&environment);
+    /// assert_eq!(answer, Ok(3.0));
+    /// ```
+    pub fn insert_function(&mut self, name: &str, arity: Option<usize>, body: fn(&[f64]) -> f64) {
+        self.functions.insert(name.to_string(), Function { arity, body });
+    }
+}
+
+/// The syntax tree of an arithmetic expression.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{Environment, Expr};
+///
+/// let mut environment = Environment::new();
+/// environment.insert_variable("x", 3.0);
+///
+/// let expr = Expr::parse("2 * x^2 - max(x, 4) / 2").unwrap();
+/// assert_eq!(expr.eval(&environment), Ok(16.0));
+/// assert_eq!(expr.to_rpn(), "2 x 2 ^ * x 4 max:2 2 / -".to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Variable { name: String, position: usize },
+    Neg(Box<Expr>),
+    Binary { op: BinaryOp, left: Box<Expr>, right: Box<Expr> },
+    Call { name: String, args: Vec<Expr>, position: usize },
+}
+
+impl Expr {
+    /// Parse an infix expression of numbers, variables, function calls,
+    /// `+ - * / % ^`, unary `-` and `+`, and parentheses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::{Expr, ExpressionErrorKind};
+    ///
+    /// let answer = Expr::parse("(1 + 2").unwrap_err();
+    /// assert_eq!(answer.kind, ExpressionErrorKind::UnclosedParenthesis);
+    /// assert_eq!(answer.position, 0);
+    ///
+    /// let answer = Expr::parse("1 + * 2").unwrap_err();
+    /// assert_eq!(answer.kind, ExpressionErrorKind::UnexpectedToken);
+    /// assert_eq!(answer.position, 4);
+    /// ```
+    pub fn parse(source: &str) -> Result<Expr, ExpressionError> {
+        let mut parser = Parser { tokens: tokenize(source)?, index: 0, end: source.chars().count(), depth: 0 };
+        let expr = parser.expression(0)?;
+
+        match parser.tokens.get(parser.index) {
+            Some(&(Token::RightParen, position)) => {
+                Err(ExpressionError { position, kind: ExpressionErrorKind::UnopenedParenthesis })
+            }
+            Some(&(_, position)) => Err(ExpressionError { position, kind: ExpressionErrorKind::UnexpectedToken }),
+            None => Ok(expr),
+        }
+    }
+
+    /// Evaluate the expression. Arithmetic follows `f64`, so dividing by
+    /// zero gives an infinity or NaN rather than an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::{Environment, Expr, ExpressionErrorKind};
+    ///
+    /// let environment = Environment::new();
+    ///
+    /// let answer = Expr::parse("-2^2 + sqrt(16)").unwrap().eval(&environment);
+    /// assert_eq!(answer, Ok(0.0));
+    ///
+    /// let answer = Expr::parse("1 + y").unwrap().eval(&environment).unwrap_err();
+    /// assert_eq!(answer.kind, ExpressionErrorKind::UnknownVariable);
+    /// assert_eq!(answer.position, 4);
+    /// ```
+    pub fn eval(&self, environment: &Environment) -> Result<f64, ExpressionError> {
+        let mut values = vec![];
+        let mut stack = vec![Step::Visit(self)];
+
+        while let Some(step) = stack.pop() {
+            match step {
+                Step::Visit(Expr::Number(value)) => values.push(*value),
+                Step::Visit(Expr::Variable { name, position }) => {
+                    let value = environment.variables.get(name).copied().ok_or(ExpressionError {
+                        position: *position,
+                        kind: ExpressionErrorKind::UnknownVariable,
+                    })?;
+
+                    values.push(value);
+                }
+                Step::Visit(expr @ Expr::Call { name, args, position }) => {
+                    let error = |kind| ExpressionError { position: *position, kind };
+                    let function = environment.functions.get(name).ok_or(error(ExpressionErrorKind::UnknownFunction))?;
+
+                    if function.arity.is_some_and(|arity| arity != args.len()) {
+                        return Err(error(ExpressionErrorKind::WrongArgumentCount));
+                    }
+
+                    expr.push_steps(&mut stack);
+                }
+                Step::Visit(expr) => expr.push_steps(&mut stack),
+                Step::Apply(Expr::Neg(_)) => {
+                    let operand = values.pop().unwrap();
+                    values.push(-operand);
+                }
+                Step::Apply(Expr::Binary { op, .. }) => {
+                    let right = values.pop().unwrap();
+                    let left = values.pop().unwrap();
+                    values.push(op.apply(left, right));
+                }
+                Step::Apply(Expr::Call { name, args, .. }) => {
+                    let args = values.split_off(values.len() - args.len());
+                    values.push((environment.functions[name].body)(&args));
+                }
+                Step::Apply(_) => unreachable!(),
+            }
+        }
+
+        Ok(values.pop().unwrap())
+    }
+
+    /// Return the expression in reverse Polish notation, with tokens split
+    /// by spaces. Unary minus is written as `neg` and a call as its name and
+    /// number of arguments, like `max:2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::Expr;
+    ///
+    /// let answer = Expr::parse("-(a + 1.5) * 2^3^2").unwrap().to_rpn();
+    /// assert_eq!(answer, "a 1.5 + neg 2 3 2 ^ ^ *".to_string());
+    /// ```
+    pub fn to_rpn(&self) -> String {
+        let mut tokens = vec![];
+
+        self.push_rpn(&mut tokens);
+        tokens.join(" ")
+    }
+
+    fn push_rpn(&self, tokens: &mut Vec<String>) {
+        let mut stack = vec![Step::Visit(self)];
+
+        while let Some(step) = stack.pop() {
+            match step {
+                Step::Visit(Expr::Number(value)) => tokens.push(value.to_string()),
+                Step::Visit(Expr::Variable { name, .. }) => tokens.push(name.clone()),
+                Step::Visit(expr) => expr.push_steps(&mut stack),
+                Step::Apply(Expr::Neg(_)) => tokens.push("neg".to_string()),
+                Step::Apply(Expr::Binary { op, .. }) => tokens.push(op.symbol().to_string()),
+                Step::Apply(Expr::Call { name, args, .. }) => tokens.push(format!("{}:{}", name, args.len())),
+                Step::Apply(_) => unreachable!(),
+            }
+        }
+    }
+
+    /// Push the steps that visit the operands of an operator or call from
+    /// left to right and then apply it.
+    fn push_steps<'a>(&'a self, stack: &mut Vec<Step<'a>>) {
+        stack.push(Step::Apply(self));
+
+        match self {
+            Expr::Number(_) | Expr::Variable { .. } => {}
+            Expr::Neg(operand) => stack.push(Step::Visit(operand)),
+            Expr::Binary { left, right, .. } => stack.extend([Step::Visit(right), Step::Visit(left)]),
+            Expr::Call { args, .. } => stack.extend(args.iter().rev().map(Step::Visit)),
+        }
+    }
+
+    /// Return the names of the variables in order of first appearance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::Expr;
+    ///
+    /// let expr = Expr::parse("y * sin(x) + y").unwrap();
+    /// assert_eq!(expr.variables(), vec!["y", "x"]);
+    /// ```
+    pub fn variables(&self) -> Vec<&str> {
+        let mut result = vec![];
+        let mut stack = vec![self];
+
+        while let Some(expr) = stack.pop() {
+            match expr {
+                Expr::Number(_) => {}
+                Expr::Variable { name, .. } => {
+                    if !result.contains(&name.as_str()) {
+                        result.push(name.as_str());
+                    }
+                }
+                Expr::Neg(operand) => stack.push(operand),
+                Expr::Binary { left, right, .. } => stack.extend([right.as_ref(), left.as_ref()]),
+                Expr::Call { args, .. } => stack.extend(args.iter().rev()),
+            }
+        }
+
+        result
+    }
+}
+
+/// Drop the operands with an explicit stack, since a long chain like
+/// `1 + 1 + ...` is as deep as it is long.
+impl Drop for Expr {
+    fn drop(&mut self) {
+        let mut stack = vec![];
+        self.take_operands(&mut stack);
+
+        // Each popped node is dropped with its operands already taken.
+        while let Some(mut expr) = stack.pop() {
+            expr.take_operands(&mut stack);
+        }
+    }
+}
+
+impl Expr {
+    /// Move the operands that have operands of their own onto `stack`.
+    fn take_operands(&mut self, stack: &mut Vec<Expr>) {
+        let mut take = |operand: &mut Box<Expr>| {
+            if matches!(**operand, Expr::Neg(_) | Expr::Binary { .. } | Expr::Call { .. }) {
+                stack.push(std::mem::replace(operand.as_mut(), Expr::Number(0.0)));
+            }
+        };
+
+        match self {
+            Expr::Number(_) | Expr::Variable { .. } => {}
+            Expr::Neg(operand) => take(operand),
+            Expr::Binary { left, right, .. } => {
+                take(left);
+                take(right);
+            }
+            Expr::Call { args, .. } => stack.append(args),
+        }
+    }
+}
+
+/// A step of a walk over an [`Expr`]: visit a node, or apply an operator
+/// or call once its operands have been visited.
+enum Step<'a> {
+    Visit(&'a Expr),
+    Apply(&'a Expr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+/// Split the expression into tokens with their char indices.
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ExpressionError> {
+    let mut tokens = vec![];
+    let mut chars = source.char_indices().enumerate().peekable();
+
+    while let Some((position, (i, c))) = chars.next() {
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '0'..='9' | '.' => {
+                let mut end = i + 1;
+
+                while let Some((_, (j, c))) = chars.next_if(|&(_, (_, c))| c.is_ascii_digit() || c == '.') {
+                    end = j + c.len_utf8();
+                }
+
+                // An exponent needs a digit, so `2e` stays a number and a variable.
+                if let Some(exponent) = source[end..].strip_prefix(['e', 'E']) {
+                    let sign = usize::from(exponent.starts_with(['+', '-']));
+
+                    if exponent[sign..].starts_with(|c: char| c.is_ascii_digit()) {
+                        chars.nth(sign);
+                        end += 1 + sign;
+
+                        while let Some((_, (j, _))) = chars.next_if(|&(_, (_, c))| c.is_ascii_digit()) {
+                            end = j + 1;
+                        }
+                    }
+                }
+
+                let value = source[i..end].parse().map_err(|_| ExpressionError {
+                    position,
+                    kind: ExpressionErrorKind::InvalidNumber,
+                })?;
+
+                Token::Number(value)
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                let mut name = c.to_string();
+
+                while let Some((_, (_, c))) = chars.next_if(|&(_, (_, c))| c.is_alphanumeric() || c == '_') {
+                    name.push(c);
+                }
+
+                Token::Ident(name)
+            }
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            ',' => Token::Comma,
+            _ if BinaryOp::from_char(c).is_some() => Token::Op(c),
+            _ => return Err(ExpressionError { position, kind: ExpressionErrorKind::UnexpectedChar }),
+        };
+
+        tokens.push((token, position));
+    }
+
+    Ok(tokens)
+}
+
+/// A Pratt parser over the tokens of an expression.
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+    end: usize,
+    // The number of `expression` calls in progress.
+    depth: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<(Token, usize)> {
+        let token = self.tokens.get(self.index).cloned();
+        self.index += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(token, _)| token)
+    }
+
+    /// Parse an expression whose operators bind at least as tight as
+    /// `min_power`.
+    fn expression(&mut self, min_power: u8) -> Result<Expr, ExpressionError> {
+        if self.depth == MAX_DEPTH {
+            let position = self.tokens.get(self.index).map_or(self.end, |&(_, position)| position);
+            return Err(ExpressionError { position, kind: ExpressionErrorKind::TooDeep });
+        }
+
+        self.depth += 1;
+        let result = self.operators(min_power);
+        self.depth -= 1;
+        result
+    }
+
+    fn operators(&mut self, min_power: u8) -> Result<Expr, ExpressionError> {
+        let Some((token, position)) = self.next() else {
+            return Err(ExpressionError { position: self.end, kind: ExpressionErrorKind::UnexpectedEnd });
+        };
+
+        let mut left = match token {
+            Token::Number(value) => Expr::Number(value),
+            Token::Ident(name) if self.peek() == Some(&Token::LeftParen) => {
+                let open = self.next().unwrap().1;
+                Expr::Call { name, args: self.arguments(open)?, position }
+            }
+            Token::Ident(name) => Expr::Variable { name, position },
+            Token::Op('-') => Expr::Neg(Box::new(self.expression(UNARY_POWER)?)),
+            Token::Op('+') => self.expression(UNARY_POWER)?,
+            Token::LeftParen => {
+                let expr = self.expression(0)?;
+                self.close(position)?;
+                expr
+            }
+            _ => return Err(ExpressionError { position, kind: ExpressionErrorKind::UnexpectedToken }),
+        };
+
+        // A chain like `1 + 1 + ...` grows the tree in this loop without
+        // nesting calls, so only its operands count towards the depth.
+        while let Some(&(Token::Op(c), _)) = self.tokens.get(self.index) {
+            let op = BinaryOp::from_char(c).unwrap();
+            let (left_power, right_power) = op.binding_power();
+
+            if left_power < min_power {
+                break;
+            }
+
+            self.index += 1;
+            let right = self.expression(right_power)?;
+            left = Expr::Binary { op, left: Box::new(left), right: Box::new(right) };
+        }
+
+        Ok(left)
+    }
+
+    /// Parse the arguments of a call after its `(` at `open`.
+    fn arguments(&mut self, open: usize) -> Result<Vec<Expr>, ExpressionError> {
+        let mut args = vec![];
+
+        if self.peek() == Some(&Token::RightParen) {
+            self.index += 1;
+            return Ok(args);
+        }
+
+        loop {
+            args.push(self.expression(0)?);
+
+            if self.peek() != Some(&Token::Comma) {
+                self.close(open)?;
+                return Ok(args);
+            }
+
+            self.index += 1;
+        }
+    }
+
+    /// Consume the `)` matching the `(` at `open`.
+    fn close(&mut self, open: usize) -> Result<(), ExpressionError> {
+        match self.next() {
+            Some((Token::RightParen, _)) => Ok(()),
+            Some((_, position)) => Err(ExpressionError { position, kind: ExpressionErrorKind::UnexpectedToken }),
+            None => Err(ExpressionError { position: open, kind: ExpressionErrorKind::UnclosedParenthesis }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(source: &str) -> Result<f64, ExpressionError> {
+        let mut environment = Environment::new();
+        environment.insert_variable("x", 2.0);
+        environment.insert_variable("y_1", 10.0);
+
+        Expr::parse(source)?.eval(&environment)
+    }
+
+    fn error(position: usize, kind: ExpressionErrorKind) -> Result<f64, ExpressionError> {
+        Err(ExpressionError { position, kind })
+    }
+
+    #[test]
+    fn test_eval() {
+        assert_eq!(eval("42"), Ok(42.0));
+        assert_eq!(eval("1 + 2 * 3"), Ok(7.0));
+        assert_eq!(eval("(1 + 2) * 3"), Ok(9.0));
+        assert_eq!(eval("10 - 4 - 3"), Ok(3.0));
+        assert_eq!(eval("2 ^ 3 ^ 2"), Ok(512.0));
+        assert_eq!(eval("-x^2"), Ok(-4.0));
+        assert_eq!(eval("(-x)^2"), Ok(4.0));
+        assert_eq!(eval("2^-1"), Ok(0.5));
+        assert_eq!(eval("--+x"), Ok(2.0));
+        assert_eq!(eval("7 % 4 * y_1"), Ok(30.0));
+        assert_eq!(eval("1.5e2 + .5 + 2E-1"), Ok(150.7));
+        assert_eq!(eval("pow(x, 10) / max(1, y_1, 3)"), Ok(102.4));
+        assert_eq!(eval("min()"), Ok(f64::INFINITY));
+        assert_eq!(eval("1 / 0"), Ok(f64::INFINITY));
+        assert_eq!(eval("floor(pi)"), Ok(3.0));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(eval(""), error(0, ExpressionErrorKind::UnexpectedEnd));
+        assert_eq!(eval("1 +"), error(3, ExpressionErrorKind::UnexpectedEnd));
+        assert_eq!(eval("1 # 2"), error(2, ExpressionErrorKind::UnexpectedChar));
+        assert_eq!(eval("1.2.3"), error(0, ExpressionErrorKind::InvalidNumber));
+        assert_eq!(eval("1 2"), error(2, ExpressionErrorKind::UnexpectedToken));
+        assert_eq!(eval("2x"), error(1, ExpressionErrorKind::UnexpectedToken));
+        assert_eq!(eval("(1))"), error(3, ExpressionErrorKind::UnopenedParenthesis));
+        assert_eq!(eval("((1)"), error(0, ExpressionErrorKind::UnclosedParenthesis));
+        assert_eq!(eval("(1 2)"), error(3, ExpressionErrorKind::UnexpectedToken));
+        assert_eq!(eval("max(1, 2"), error(3, ExpressionErrorKind::UnclosedParenthesis));
+        assert_eq!(eval("max(1,)"), error(6, ExpressionErrorKind::UnexpectedToken));
+        assert_eq!(eval("мир + 1 +"), error(9, ExpressionErrorKind::UnexpectedEnd));
+        assert_eq!(eval("é + #"), error(4, ExpressionErrorKind::UnexpectedChar));
+        assert_eq!(eval("é + 2 2"), error(6, ExpressionErrorKind::UnexpectedToken));
+    }
+
+    #[test]
+    fn test_eval_errors() {
+        assert_eq!(eval("x + z"), error(4, ExpressionErrorKind::UnknownVariable));
+        assert_eq!(eval("1 + foo(1)"), error(4, ExpressionErrorKind::UnknownFunction));
+        assert_eq!(eval("sqrt(1, 2)"), error(0, ExpressionErrorKind::WrongArgumentCount));
+
+        let answer = Expr::parse("1 + (2").unwrap_err();
+        assert_eq!(answer.to_string(), "unclosed parenthesis at position 4");
+    }
+
+    #[test]
+    fn test_too_deep() {
+        let nested = |depth: usize| "(".repeat(depth) + "1" + &")".repeat(depth);
+
+        assert_eq!(eval(&nested(255)), Ok(1.0));
+        assert_eq!(eval(&nested(256)), error(256, ExpressionErrorKind::TooDeep));
+        assert_eq!(eval(&nested(100000)), error(256, ExpressionErrorKind::TooDeep));
+        assert_eq!(eval(&("-".repeat(200000) + "1")), error(256, ExpressionErrorKind::TooDeep));
+        assert_eq!(eval(&("2^".repeat(100000) + "1")), error(512, ExpressionErrorKind::TooDeep));
+        assert_eq!(eval(&("1+".repeat(100000) + "1")), Ok(100001.0));
+        assert_eq!(eval(&("1+(".repeat(256) + "1" + &")".repeat(256))), error(384, ExpressionErrorKind::TooDeep));
+        assert_eq!(eval(&("sqrt(".repeat(100000) + "1")), error(1280, ExpressionErrorKind::TooDeep));
+        assert_eq!(eval(&("1+".repeat(299) + "1")), Ok(300.0));
+        assert_eq!(eval(&("max(".to_string() + &"1,".repeat(100000) + "2)")), Ok(2.0));
+    }
+
+    #[test]
+    fn test_to_rpn() {
+        assert_eq!(Expr::parse("1 - 2 - 3").unwrap().to_rpn(), "1 2 - 3 -".to_string());
+        assert_eq!(Expr::parse("1 - (2 - 3)").unwrap().to_rpn(), "1 2 3 - -".to_string());
+        assert_eq!(Expr::parse("f() + g(x, -1)").unwrap().to_rpn(), "f:0 x 1 neg g:2 +".to_string());
+    }
+
+    #[test]
+    fn test_ast() {
+        let answer = Expr::parse("-a * 2").unwrap();
+        let expected = Expr::Binary {
+            op: BinaryOp::Mul,
+            left: Box::new(Expr::Neg(Box::new(Expr::Variable { name: "a".to_string(), position: 1 }))),
+            right: Box::new(Expr::Number(2.0)),
+        };
+        assert_eq!(answer, expected);
+    }
+}
@@ -1,6 +1,61 @@
 use std::collections::HashMap;
 use std::cmp::Ordering::{Less, Equal, Greater};
 
+use crate::collections::FrequencyTable;
+
+mod aho_corasick;
+mod analysis;
+mod brackets;
+mod case;
+mod diff;
+mod distance;
+mod expression;
+mod phonetic;
+mod regex;
+mod rolling_hash;
+mod rope;
+mod search;
+mod slug;
+mod suffix_array;
+mod suffix_automaton;
+mod template;
+mod tokenizer;
+mod window;
+mod wrap;
+
+pub use aho_corasick::{AhoCorasick, AhoCorasickStream, MatchKind, PatternMatch};
+pub use analysis::{char_ngrams, top_words, type_token_ratio, word_frequencies, word_ngrams, ENGLISH_STOP_WORDS};
+pub use brackets::{
+    balanced_brackets, balanced_brackets_with, count_balanced, longest_valid_parentheses, match_brackets, min_insertions,
+    rank_balanced, repair, unrank_balanced, BalancedBrackets, BracketEdit, BracketError, BracketErrorKind, BracketStream,
+    BracketValidator,
+};
+pub use case::{
+    identifier_words, to_camel_case, to_case, to_kebab_case, to_pascal_case, to_screaming_snake_case, to_snake_case,
+    to_title_case, to_train_case, Case,
+};
+pub use diff::{apply_patch, diff, diff_chars, diff_lines, unified_diff, DiffOp, PatchError, PatchErrorKind};
+pub use distance::{
+    damerau_levenshtein, damerau_levenshtein_alignment, hamming, jaro, jaro_winkler, levenshtein, levenshtein_alignment,
+    levenshtein_bounded, normalized_damerau_levenshtein, normalized_levenshtein, AlignOp,
+};
+pub use expression::{BinaryOp, Environment, Expr, ExpressionError, ExpressionErrorKind};
+pub use phonetic::{double_metaphone, group_by_phonetic_key, nysiis, refined_soundex, soundex, PhoneticAlgorithm};
+pub use regex::{Regex, RegexError, RegexErrorKind};
+pub use rolling_hash::RollingHash;
+pub use rope::{CharSource, Rope, RopeChars};
+pub use search::{
+    failure_function, find_all, find_all_bytes, z_array, HorspoolSearcher, KmpSearcher, MatchMode, SearchAlgorithm,
+    ZSearcher,
+};
+pub use slug::{slugify, slugify_with, transliterate, SlugOptions};
+pub use suffix_array::{lcp_array, longest_common_substring, suffix_array, SuffixArray};
+pub use suffix_automaton::SuffixAutomaton;
+pub use template::{MissingKey, Template, TemplateError, TemplateErrorKind, Value};
+pub use tokenizer::{graphemes, split_word_bounds, words, TextUnit};
+pub use window::{covering_windows, covering_windows_slice, min_window};
+pub use wrap::{display_width, fill, justify, wrap};
+
 /// Return the longest palindrom substring.
 ///
 /// # Examples
@@ -100,7 +155,8 @@ pub fn is_anagram(s1: String, s2: String) -> bool {
 /// ```
 pub fn is_word_pattern(pattern: &str, s: &str) -> bool {
     let is_valid_string = s.chars().all(|c| c.is_alphanumeric() || c.is_whitespace());
-    let is_valid_count = s.split_ascii_whitespace().count() == pattern.len();
+    let words = words(s);
+    let is_valid_count = words.len() == pattern.chars().count();
 
     if !is_valid_string || !is_valid_count {
         return false;
@@ -111,7 +167,7 @@ pub fn is_word_pattern(pattern: &str, s: &str) -> bool {
 
     pattern
         .chars()
-        .zip(s.split_ascii_whitespace())
+        .zip(words)
         .all(|(c, w)| {
             if pattern_hash.entry(c).or_insert(w) != &w {
                 return false;
@@ -121,7 +177,7 @@ pub fn is_word_pattern(pattern: &str, s: &str) -> bool {
                 return false;
             }
 
-            return true;
+            true
         })
 }
 
@@ -151,43 +207,44 @@ pub fn is_word_pattern(pattern: &str, s: &str) -> bool {
 /// assert_eq!(answer, false);
 /// ```
 pub fn is_palindrome(word: &str, is_case_ignore: bool) -> bool {
-    if word.is_empty() {
-        return true;
-    }
-
-    let chars: Vec<char> = word.chars().collect();
-    let (mut first_char, mut last_char) = (0, chars.len() - 1);
-
-    while first_char < last_char {
-        if !chars[first_char].is_alphabetic() {
-            first_char += 1;
-            continue;
-        }
+    is_palindrome_in(word, is_case_ignore)
+}
 
-        if !chars[last_char].is_alphabetic() {
-            last_char -= 1;
-            continue;
-        }
+/// Return whether true or false if the text of any [`CharSource`], such as a
+/// [`Rope`], is palindrome or not, like [`is_palindrome`].
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{is_palindrome_in, Rope};
+///
+/// let mut rope = Rope::from("Was it a car");
+/// rope.insert(12, " or a cat I saw");
+///
+/// let answer = is_palindrome_in(&rope, false);
+/// assert_eq!(answer, true);
+/// ```
+pub fn is_palindrome_in<S: CharSource + ?Sized>(word: &S, is_case_ignore: bool) -> bool {
+    let mut chars = word.source_chars().filter(|c| c.is_alphabetic());
 
-        if is_case_ignore && (chars[first_char] != chars[last_char]) {
+    while let (Some(first_char), Some(last_char)) = (chars.next(), chars.next_back()) {
+        if is_case_ignore && first_char != last_char {
             return false;
         }
 
-        if !is_case_ignore
-            && chars[first_char].to_lowercase().to_string()
-                != chars[last_char].to_lowercase().to_string()
-        {
+        if !is_case_ignore && !first_char.to_lowercase().eq(last_char.to_lowercase()) {
             return false;
         }
-
-        first_char += 1;
-        last_char -= 1;
     }
 
     true
 }
 
-/// Return the length of the last word
+/// Return the length of the last word in chars.
+///
+/// Words are found by [`words`]. An apostrophe or period between letters is
+/// part of the word, so `"don't"` has length 5, while other punctuation such
+/// as a hyphen splits words, so the last word of `"e-mail"` is `"mail"`.
 ///
 /// # Examples
 ///
@@ -197,20 +254,31 @@ pub fn is_palindrome(word: &str, is_case_ignore: bool) -> bool {
 /// let answer = length_of_last_word("Hello world!");
 /// assert_eq!(answer, 5);
 ///
+/// let answer = length_of_last_word("Привет мир");
+/// assert_eq!(answer, 3);
+///
 /// let answer = length_of_last_word("");
 /// assert_eq!(answer, 0);
 /// ```
 pub fn length_of_last_word(s: &str) -> i32 {
-    let result: String = s
-        .chars()
-        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
-        .collect();
+    length_of_last_word_in(s, TextUnit::Chars) as i32
+}
 
-    if let Some(last) = result.split_whitespace().last() {
-        last.len() as i32
-    } else {
-        0
-    }
+/// Return the length of the last word in the given unit.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{length_of_last_word_in, TextUnit};
+///
+/// let answer = length_of_last_word_in("voila\u{300}", TextUnit::Chars);
+/// assert_eq!(answer, 6);
+///
+/// let answer = length_of_last_word_in("voila\u{300}", TextUnit::Graphemes);
+/// assert_eq!(answer, 5);
+/// ```
+pub fn length_of_last_word_in(s: &str, unit: TextUnit) -> usize {
+    words(s).last().map_or(0, |last| unit.count(last))
 }
 
 /// Return whether true or false if s1 has inclusion into s2
@@ -227,37 +295,25 @@ pub fn length_of_last_word(s: &str) -> i32 {
 /// assert_eq!(answer, false);
 /// ```
 pub fn check_inslusion(s1: String, s2: String) -> bool {
-    if s1.len() > s2.len() {
-        return false;
-    }
-
+    let s1: Vec<char> = s1.chars().collect();
+    let s2: Vec<char> = s2.chars().collect();
     let n = s1.len();
-    let mut dict = [0; 26];
-
-    s1.chars().for_each(|c| {
-        dict[c as usize - 'a' as usize] += 1;
-    });
-    s2.chars().take(n).for_each(|c| {
-        dict[c as usize - 'a' as usize] -= 1;
-    });
-
-    if dict.into_iter().all(|x| x == 0) {
-        return true;
-    }
+    let mut table = FrequencyTable::new(&s1);
 
-    let start = s2.chars();
-    let end = s2.chars().skip(n);
+    // A window of exactly n chars that covers s1 is a permutation of it.
+    for (end, c) in s2.iter().enumerate() {
+        table.push(c);
 
-    for (out, inn) in start.zip(end) {
-        dict[out as usize - 'a' as usize] += 1;
-        dict[inn as usize - 'a' as usize] -= 1;
+        if end >= n {
+            table.pop(&s2[end - n]);
+        }
 
-        if dict.into_iter().all(|x| x == 0) {
+        if end + 1 >= n && table.is_covered() {
             return true;
         }
     }
 
-    false
+    n == 0
 }
 
 /// Return the desired index from the vector through binary search. 
@@ -302,27 +358,30 @@ pub fn binary_search(nums: Vec<i32>, target: i32) -> i32 {
 ///
 /// let answer = is_valid_parentheses("{{[]})".to_string());
 /// assert_eq!(answer, false);
+///
+/// let answer = is_valid_parentheses("f(a[0])".to_string());
+/// assert_eq!(answer, true);
 /// ```
 pub fn is_valid_parentheses(s: String) -> bool {
-    if s.is_empty() && s.len() & 1 == 1 {
-        return false;
-    }
-
-    let mut v = Vec::with_capacity(s.len());
-
-    for c in s.chars() {
-        match c {
-            '(' | '{' | '[' => v.push(c),
-            _ => match v.pop() {
-                Some('(') if c == ')' => (),
-                Some('{') if c == '}' => (),
-                Some('[') if c == ']' => (),
-                _ => return false,
-            },
-        }
-    }
+    is_valid_parentheses_in(&s)
+}
 
-    true
+/// Check whether parentheses in the text of any [`CharSource`], such as a
+/// [`Rope`], are valid or not, like [`is_valid_parentheses`].
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{is_valid_parentheses_in, Rope};
+///
+/// let answer = is_valid_parentheses_in("f(a[0])");
+/// assert_eq!(answer, true);
+///
+/// let answer = is_valid_parentheses_in(&Rope::from("(]"));
+/// assert_eq!(answer, false);
+/// ```
+pub fn is_valid_parentheses_in<S: CharSource + ?Sized>(s: &S) -> bool {
+    BracketValidator::new().validate(s).is_ok()
 }
 
 /// Encode a vector of string into encoded string
@@ -363,7 +422,9 @@ pub fn encode(input: Vec<&str>) -> String {
 /// let answer = decode(&mock_value);
 /// assert_eq!(answer, vec![""]);
 /// ```
-pub fn decode<'a>(input: &'a String ) -> Vec<&'a str> {
+// Kept as `&String` so that existing callers and fn pointers still compile.
+#[allow(clippy::ptr_arg, clippy::needless_lifetimes)]
+pub fn decode<'a>(input: &'a String) -> Vec<&'a str> {
     let mut result = vec![];
     let mut counter = 0;
 
@@ -374,7 +435,7 @@ pub fn decode<'a>(input: &'a String ) -> Vec<&'a str> {
             subcounter += 1;
         }
 
-        let word_size: usize = *(&input[counter..subcounter].parse().unwrap_or(0));
+        let word_size: usize = input[counter..subcounter].parse().unwrap_or(0);
         let word = &input[subcounter + 1..subcounter + 1 + word_size];
         result.push(word);
 
@@ -385,6 +446,7 @@ pub fn decode<'a>(input: &'a String ) -> Vec<&'a str> {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -395,6 +457,9 @@ mod tests {
 
         let answer = is_word_pattern("aaa", "lol kek lol");
         assert_eq!(answer, false);
+
+        let answer = is_word_pattern("ab", "привет\u{3000}мир");
+        assert_eq!(answer, true);
     }
 
     #[test]
@@ -416,6 +481,11 @@ mod tests {
 
         let answer = is_palindrome("hello", false);
         assert_eq!(answer, false);
+
+        let mut rope = Rope::from("А роза упала");
+        rope.insert(12, " на лапу Азора");
+        assert_eq!(is_palindrome_in(&rope, false), true);
+        assert_eq!(is_palindrome_in(&rope, true), false);
     }
 
     #[test]
@@ -425,6 +495,21 @@ mod tests {
 
         let answer = length_of_last_word("");
         assert_eq!(answer, 0);
+
+        let answer = length_of_last_word("Привет мир");
+        assert_eq!(answer, 3);
+
+        let answer = length_of_last_word("I don't");
+        assert_eq!(answer, 5);
+
+        let answer = length_of_last_word("e-mail");
+        assert_eq!(answer, 4);
+
+        let answer = length_of_last_word_in("🇺🇦 e\u{301}te\u{301}", TextUnit::Graphemes);
+        assert_eq!(answer, 3);
+
+        let answer = length_of_last_word_in("hello 한국어", TextUnit::Width);
+        assert_eq!(answer, 6);
     }
 
     #[test]
@@ -434,6 +519,12 @@ mod tests {
 
         let answer = check_inslusion("abbc".to_string(), "ppwwm".to_string());
         assert_eq!(answer, false);
+
+        let answer = check_inslusion("мир".to_string(), "привет рим".to_string());
+        assert_eq!(answer, true);
+
+        let answer = check_inslusion("abc".to_string(), "abxc".to_string());
+        assert_eq!(answer, false);
     }
 
     #[test]
@@ -473,6 +564,13 @@ mod tests {
 
         let answer = is_valid_parentheses("{{[]})".to_string());
         assert_eq!(answer, false);
+
+        let mut rope = Rope::from("()");
+        rope.insert(1, &"[{}]".repeat(300));
+        assert_eq!(is_valid_parentheses_in(&rope), true);
+
+        assert_eq!(is_valid_parentheses_in("(a)"), true);
+        assert_eq!(is_valid_parentheses_in("(("), false);
     }
 
     #[test]
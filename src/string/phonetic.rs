@@ -0,0 +1,861 @@
+use std::collections::HashMap;
+
+use super::transliterate;
+
+/// A phonetic encoding of names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PhoneticAlgorithm {
+    Soundex,
+    RefinedSoundex,
+    /// The primary key of Double Metaphone.
+    DoubleMetaphone,
+    Nysiis,
+}
+
+impl PhoneticAlgorithm {
+    /// Return the phonetic key of the name.
+    pub fn encode(self, name: &str) -> String {
+        match self {
+            PhoneticAlgorithm::Soundex => soundex(name),
+            PhoneticAlgorithm::RefinedSoundex => refined_soundex(name),
+            PhoneticAlgorithm::DoubleMetaphone => double_metaphone(name).0,
+            PhoneticAlgorithm::Nysiis => nysiis(name),
+        }
+    }
+}
+
+/// Return the uppercase ASCII letters of the name, transliterating accented,
+/// Cyrillic and Greek letters.
+fn ascii_letters(name: &str) -> Vec<u8> {
+    transliterate(name)
+        .bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|b| b.to_ascii_uppercase())
+        .collect()
+}
+
+/// Return the American Soundex code of the name: its first letter and three
+/// digits for the following consonant sounds, e.g. `R163` for "Robert".
+/// Return an empty string if the name has no letters.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::soundex;
+///
+/// assert_eq!(soundex("Robert"), "R163".to_string());
+/// assert_eq!(soundex("Rupert"), "R163".to_string());
+/// assert_eq!(soundex("Ashcraft"), "A261".to_string());
+/// assert_eq!(soundex("Lee"), "L000".to_string());
+/// ```
+pub fn soundex(name: &str) -> String {
+    const CODES: &[u8; 26] = b"01230120022455012623010202";
+
+    let letters = ascii_letters(name);
+    let Some(&first) = letters.first() else {
+        return String::new();
+    };
+
+    let mut result = vec![first];
+    let mut last = CODES[(first - b'A') as usize];
+
+    for &letter in &letters[1..] {
+        if result.len() == 4 {
+            break;
+        }
+
+        // H and W don't separate equal codes, vowels do.
+        if letter == b'H' || letter == b'W' {
+            continue;
+        }
+
+        let code = CODES[(letter - b'A') as usize];
+
+        if code != b'0' && code != last {
+            result.push(code);
+        }
+
+        last = code;
+    }
+
+    result.resize(4, b'0');
+    String::from_utf8(result).unwrap()
+}
+
+/// Return the Refined Soundex code of the name: its first letter followed by
+/// a digit for every letter, including the first one, with repeated digits
+/// collapsed. It's not truncated and separates more sounds than Soundex.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::refined_soundex;
+///
+/// assert_eq!(refined_soundex("Braz"), "B1905".to_string());
+/// assert_eq!(refined_soundex("Caren"), "C30908".to_string());
+/// ```
+pub fn refined_soundex(name: &str) -> String {
+    const CODES: &[u8; 26] = b"01360240043788015936020505";
+
+    let letters = ascii_letters(name);
+    let Some(&first) = letters.first() else {
+        return String::new();
+    };
+
+    let mut result = vec![first];
+    let mut last = 0;
+
+    for &letter in &letters {
+        let code = CODES[(letter - b'A') as usize];
+
+        if code != last {
+            result.push(code);
+            last = code;
+        }
+    }
+
+    String::from_utf8(result).unwrap()
+}
+
+fn is_nysiis_vowel(b: u8) -> bool {
+    matches!(b, b'A' | b'E' | b'I' | b'O' | b'U')
+}
+
+/// Return the NYSIIS code of the name, at most six letters long.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::nysiis;
+///
+/// assert_eq!(nysiis("Macintosh"), "MCANT".to_string());
+/// assert_eq!(nysiis("Knuth"), "NAT".to_string());
+/// assert_eq!(nysiis("Schmidt"), "SNAD".to_string());
+/// ```
+pub fn nysiis(name: &str) -> String {
+    let mut s = ascii_letters(name);
+
+    if s.is_empty() {
+        return String::new();
+    }
+
+    for (from, to) in [("MAC", "MCC"), ("KN", "NN"), ("K", "C"), ("PH", "FF"), ("PF", "FF"), ("SCH", "SSS")] {
+        if s.starts_with(from.as_bytes()) {
+            s[..from.len()].copy_from_slice(to.as_bytes());
+            break;
+        }
+    }
+
+    for (from, to) in [("EE", "Y"), ("IE", "Y"), ("DT", "D"), ("RT", "D"), ("RD", "D"), ("NT", "D"), ("ND", "D")] {
+        if s.len() > 1 && s.ends_with(from.as_bytes()) {
+            s.truncate(s.len() - 2);
+            s.extend_from_slice(to.as_bytes());
+            break;
+        }
+    }
+
+    let mut key = vec![s[0]];
+
+    for i in 1..s.len() {
+        let (prev, current) = (s[i - 1], s[i]);
+        let next = s.get(i + 1).copied().unwrap_or(b' ');
+        let after_next = s.get(i + 2).copied().unwrap_or(b' ');
+
+        // The replacement is written over the following letters as well.
+        let replacement: &[u8] = match current {
+            b'E' if next == b'V' => b"AF",
+            c if is_nysiis_vowel(c) => b"A",
+            b'Q' => b"G",
+            b'Z' => b"S",
+            b'M' => b"N",
+            b'K' if next == b'N' => b"NN",
+            b'K' => b"C",
+            b'S' if next == b'C' && after_next == b'H' => b"SSS",
+            b'P' if next == b'H' => b"FF",
+            b'H' if !is_nysiis_vowel(prev) || !is_nysiis_vowel(next) => &[prev],
+            b'W' if is_nysiis_vowel(prev) => &[prev],
+            _ => &[current],
+        };
+
+        let n = replacement.len().min(s.len() - i);
+        s[i..i + n].copy_from_slice(&replacement[..n]);
+
+        if key.last() != Some(&s[i]) {
+            key.push(s[i]);
+        }
+    }
+
+    if key.len() > 1 && key.ends_with(b"S") {
+        key.pop();
+    }
+
+    if key.len() > 2 && key.ends_with(b"AY") {
+        key.truncate(key.len() - 2);
+        key.push(b'Y');
+    }
+
+    if key.len() > 1 && key.ends_with(b"A") {
+        key.pop();
+    }
+
+    key.truncate(6);
+    String::from_utf8(key).unwrap()
+}
+
+/// State of the Double Metaphone encoder, a port of Lawrence Philips'
+/// original rules.
+struct DoubleMetaphone {
+    chars: Vec<u8>,
+    primary: String,
+    alternate: String,
+    slavo_germanic: bool,
+}
+
+impl DoubleMetaphone {
+    const MAX_LENGTH: usize = 4;
+
+    fn new(name: &str) -> Self {
+        let cleaned: String = transliterate(name)
+            .chars()
+            .filter(|c| c.is_ascii_alphabetic() || c.is_whitespace())
+            .map(|c| if c.is_whitespace() { ' ' } else { c.to_ascii_uppercase() })
+            .collect();
+        let chars = cleaned.trim().as_bytes().to_vec();
+        let contains = |s: &[u8]| chars.windows(s.len()).any(|w| w == s);
+        let slavo_germanic = contains(b"W") || contains(b"K") || contains(b"CZ") || contains(b"WITZ");
+
+        DoubleMetaphone { chars, primary: String::new(), alternate: String::new(), slavo_germanic }
+    }
+
+    fn len(&self) -> isize {
+        self.chars.len() as isize
+    }
+
+    fn at(&self, i: isize) -> u8 {
+        if i < 0 || i >= self.len() {
+            0
+        } else {
+            self.chars[i as usize]
+        }
+    }
+
+    /// Return whether true or false if one of the options starts at `start`.
+    fn matches(&self, start: isize, options: &[&str]) -> bool {
+        options.iter().any(|option| {
+            let end = start + option.len() as isize;
+
+            start >= 0 && end <= self.len() && &self.chars[start as usize..end as usize] == option.as_bytes()
+        })
+    }
+
+    fn is_vowel(&self, i: isize) -> bool {
+        matches!(self.at(i), b'A' | b'E' | b'I' | b'O' | b'U' | b'Y')
+    }
+
+    fn is_germanic(&self) -> bool {
+        self.matches(0, &["VAN ", "VON "]) || self.matches(0, &["SCH"])
+    }
+
+    fn add(&mut self, primary: &str, alternate: &str) {
+        self.primary.push_str(primary);
+        self.alternate.push_str(alternate);
+    }
+
+    fn add_both(&mut self, code: &str) {
+        self.add(code, code);
+    }
+
+    /// Skip the letter and its double, e.g. `FF`.
+    fn skip_double(&self, i: isize) -> isize {
+        if self.at(i + 1) == self.at(i) {
+            i + 2
+        } else {
+            i + 1
+        }
+    }
+
+    fn encode(mut self) -> (String, String) {
+        let mut i = 0;
+
+        if self.matches(0, &["GN", "KN", "PN", "WR", "PS"]) {
+            i = 1;
+        }
+
+        if self.at(0) == b'X' {
+            self.add_both("S");
+            i = 1;
+        }
+
+        while i < self.len() && (self.primary.len() < Self::MAX_LENGTH || self.alternate.len() < Self::MAX_LENGTH) {
+            i = match self.at(i) {
+                b'A' | b'E' | b'I' | b'O' | b'U' | b'Y' => {
+                    if i == 0 {
+                        self.add_both("A");
+                    }
+                    i + 1
+                }
+                b'B' => {
+                    self.add_both("P");
+                    self.skip_double(i)
+                }
+                b'C' => self.handle_c(i),
+                b'D' => self.handle_d(i),
+                b'F' => {
+                    self.add_both("F");
+                    self.skip_double(i)
+                }
+                b'G' => self.handle_g(i),
+                b'H' => self.handle_h(i),
+                b'J' => self.handle_j(i),
+                b'K' => {
+                    self.add_both("K");
+                    self.skip_double(i)
+                }
+                b'L' => self.handle_l(i),
+                b'M' => {
+                    let umb = self.matches(i - 1, &["UMB"]) && (i + 1 == self.len() - 1 || self.matches(i + 2, &["ER"]));
+                    self.add_both("M");
+                    if self.at(i + 1) == b'M' || umb {
+                        i + 2
+                    } else {
+                        i + 1
+                    }
+                }
+                b'N' => {
+                    self.add_both("N");
+                    self.skip_double(i)
+                }
+                b'P' if self.at(i + 1) == b'H' => {
+                    self.add_both("F");
+                    i + 2
+                }
+                b'P' => {
+                    self.add_both("P");
+                    if self.matches(i + 1, &["P", "B"]) {
+                        i + 2
+                    } else {
+                        i + 1
+                    }
+                }
+                b'Q' => {
+                    self.add_both("K");
+                    self.skip_double(i)
+                }
+                b'R' => self.handle_r(i),
+                b'S' => self.handle_s(i),
+                b'T' => self.handle_t(i),
+                b'V' => {
+                    self.add_both("F");
+                    self.skip_double(i)
+                }
+                b'W' => self.handle_w(i),
+                b'X' => self.handle_x(i),
+                b'Z' => self.handle_z(i),
+                _ => i + 1,
+            };
+        }
+
+        self.primary.truncate(Self::MAX_LENGTH);
+        self.alternate.truncate(Self::MAX_LENGTH);
+        (self.primary, self.alternate)
+    }
+
+    fn handle_c(&mut self, i: isize) -> isize {
+        // Germanic "ach" as in "bacher", "macher", but not "Bachelor".
+        let germanic_ach = i > 1
+            && !self.is_vowel(i - 2)
+            && self.matches(i - 1, &["ACH"])
+            && (!matches!(self.at(i + 2), b'I' | b'E') || self.matches(i - 2, &["BACHER", "MACHER"]));
+
+        if self.matches(i, &["CHIA"]) || germanic_ach {
+            self.add_both("K");
+            i + 2
+        } else if i == 0 && self.matches(i, &["CAESAR"]) {
+            self.add_both("S");
+            i + 2
+        } else if self.matches(i, &["CH"]) {
+            self.handle_ch(i)
+        } else if self.matches(i, &["CZ"]) && !self.matches(i - 2, &["WICZ"]) {
+            // "Czerny"
+            self.add("S", "X");
+            i + 2
+        } else if self.matches(i + 1, &["CIA"]) {
+            // "focaccia"
+            self.add_both("X");
+            i + 3
+        } else if self.matches(i, &["CC"]) && !(i == 1 && self.at(0) == b'M') {
+            // Double "cc" but not "McClelland".
+            if self.matches(i + 2, &["I", "E", "H"]) && !self.matches(i + 2, &["HU"]) {
+                if (i == 1 && self.at(i - 1) == b'A') || self.matches(i - 1, &["UCCEE", "UCCES"]) {
+                    // "accident", "succeed"
+                    self.add_both("KS");
+                } else {
+                    // "bacci", "bertucci"
+                    self.add_both("X");
+                }
+                i + 3
+            } else {
+                self.add_both("K");
+                i + 2
+            }
+        } else if self.matches(i, &["CK", "CG", "CQ"]) {
+            self.add_both("K");
+            i + 2
+        } else if self.matches(i, &["CI", "CE", "CY"]) {
+            if self.matches(i, &["CIO", "CIE", "CIA"]) {
+                self.add("S", "X");
+            } else {
+                self.add_both("S");
+            }
+            i + 2
+        } else {
+            self.add_both("K");
+
+            if self.matches(i + 1, &[" C", " Q", " G"]) {
+                // "Mac Caffrey", "Mac Gregor"
+                i + 3
+            } else if self.matches(i + 1, &["C", "K", "Q"]) && !self.matches(i + 1, &["CE", "CI"]) {
+                i + 2
+            } else {
+                i + 1
+            }
+        }
+    }
+
+    fn handle_ch(&mut self, i: isize) -> isize {
+        // Greek roots such as "chemistry" and "chorus".
+        let greek = i == 0
+            && (self.matches(i + 1, &["HARAC", "HARIS"]) || self.matches(i + 1, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.matches(0, &["CHORE"]);
+        // Germanic, Greek or otherwise "kh" sounds.
+        let hard = self.is_germanic()
+            || self.matches(i - 2, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.matches(i + 2, &["T", "S"])
+            || ((self.matches(i - 1, &["A", "O", "U", "E"]) || i == 0)
+                && (self.matches(i + 2, &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "]) || i + 1 == self.len() - 1));
+
+        if i > 0 && self.matches(i, &["CHAE"]) {
+            // "Michael"
+            self.add("K", "X");
+        } else if greek || hard {
+            self.add_both("K");
+        } else if i == 0 {
+            self.add_both("X");
+        } else if self.matches(0, &["MC"]) {
+            self.add_both("K");
+        } else {
+            self.add("X", "K");
+        }
+
+        i + 2
+    }
+
+    fn handle_d(&mut self, i: isize) -> isize {
+        if self.matches(i, &["DG"]) {
+            if self.matches(i + 2, &["I", "E", "Y"]) {
+                // "edge"
+                self.add_both("J");
+                i + 3
+            } else {
+                // "Edgar"
+                self.add_both("TK");
+                i + 2
+            }
+        } else if self.matches(i, &["DT", "DD"]) {
+            self.add_both("T");
+            i + 2
+        } else {
+            self.add_both("T");
+            i + 1
+        }
+    }
+
+    fn handle_g(&mut self, i: isize) -> isize {
+        let next = self.at(i + 1);
+
+        if next == b'H' {
+            return self.handle_gh(i);
+        }
+
+        if next == b'N' {
+            if i == 1 && self.is_vowel(0) && !self.slavo_germanic {
+                self.add("KN", "N");
+            } else if !self.matches(i + 2, &["EY"]) && !self.slavo_germanic {
+                self.add("N", "KN");
+            } else {
+                self.add_both("KN");
+            }
+            i + 2
+        } else if self.matches(i + 1, &["LI"]) && !self.slavo_germanic {
+            // "tagliaro"
+            self.add("KL", "L");
+            i + 2
+        } else if i == 0
+            && (next == b'Y' || self.matches(i + 1, &["ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER"]))
+        {
+            // -ges-, -gep-, -gel-, -gie- at the beginning.
+            self.add("K", "J");
+            i + 2
+        } else if (self.matches(i + 1, &["ER"]) || next == b'Y')
+            && !self.matches(0, &["DANGER", "RANGER", "MANGER"])
+            && !self.matches(i - 1, &["E", "I"])
+            && !self.matches(i - 1, &["RGY", "OGY"])
+        {
+            // -ger-, -gy-
+            self.add("K", "J");
+            i + 2
+        } else if self.matches(i + 1, &["E", "I", "Y"]) || self.matches(i - 1, &["AGGI", "OGGI"]) {
+            // Italian "biaggi".
+            if self.is_germanic() || self.matches(i + 1, &["ET"]) {
+                self.add_both("K");
+            } else if self.matches(i + 1, &["IER"]) {
+                self.add_both("J");
+            } else {
+                self.add("J", "K");
+            }
+            i + 2
+        } else if next == b'G' {
+            self.add_both("K");
+            i + 2
+        } else {
+            self.add_both("K");
+            i + 1
+        }
+    }
+
+    fn handle_gh(&mut self, i: isize) -> isize {
+        if i > 0 && !self.is_vowel(i - 1) {
+            self.add_both("K");
+        } else if i == 0 {
+            self.add_both(if self.at(i + 2) == b'I' { "J" } else { "K" });
+        } else if (i > 1 && self.matches(i - 2, &["B", "H", "D"]))
+            || (i > 2 && self.matches(i - 3, &["B", "H", "D"]))
+            || (i > 3 && self.matches(i - 4, &["B", "H"]))
+        {
+            // Parker's rule: "hugh"
+        } else if i > 2 && self.at(i - 1) == b'U' && self.matches(i - 3, &["C", "G", "L", "R", "T"]) {
+            // "laugh", "cough", "rough", "tough"
+            self.add_both("F");
+        } else if i > 0 && self.at(i - 1) != b'I' {
+            self.add_both("K");
+        }
+
+        i + 2
+    }
+
+    fn handle_h(&mut self, i: isize) -> isize {
+        // Only kept when first or between vowels.
+        if (i == 0 || self.is_vowel(i - 1)) && self.is_vowel(i + 1) {
+            self.add_both("H");
+            i + 2
+        } else {
+            i + 1
+        }
+    }
+
+    fn handle_j(&mut self, i: isize) -> isize {
+        if self.matches(i, &["JOSE"]) || self.matches(0, &["SAN "]) {
+            // Spanish "Jose", "San Jacinto".
+            if (i == 0 && self.at(i + 4) == b' ') || self.len() == 4 || self.matches(0, &["SAN "]) {
+                self.add_both("H");
+            } else {
+                self.add("J", "H");
+            }
+            return i + 1;
+        }
+
+        if i == 0 {
+            self.add("J", "A");
+        } else if self.is_vowel(i - 1) && !self.slavo_germanic && matches!(self.at(i + 1), b'A' | b'O') {
+            self.add("J", "H");
+        } else if i == self.len() - 1 {
+            self.add("J", "");
+        } else if !self.matches(i + 1, &["L", "T", "K", "S", "N", "M", "B", "Z"]) && !self.matches(i - 1, &["S", "K", "L"]) {
+            self.add_both("J");
+        }
+
+        self.skip_double(i)
+    }
+
+    fn handle_l(&mut self, i: isize) -> isize {
+        if self.at(i + 1) != b'L' {
+            self.add_both("L");
+            return i + 1;
+        }
+
+        // Spanish "cabrillo", "gallegos".
+        let len = self.len();
+        let spanish = (i == len - 3 && self.matches(i - 1, &["ILLO", "ILLA", "ALLE"]))
+            || ((self.matches(len - 2, &["AS", "OS"]) || self.matches(len - 1, &["A", "O"]))
+                && self.matches(i - 1, &["ALLE"]));
+
+        self.add("L", if spanish { "" } else { "L" });
+        i + 2
+    }
+
+    fn handle_r(&mut self, i: isize) -> isize {
+        // French "Rogier".
+        if i == self.len() - 1 && !self.slavo_germanic && self.matches(i - 2, &["IE"]) && !self.matches(i - 4, &["ME", "MA"]) {
+            self.add("", "R");
+        } else {
+            self.add_both("R");
+        }
+
+        self.skip_double(i)
+    }
+
+    fn handle_s(&mut self, i: isize) -> isize {
+        if self.matches(i - 1, &["ISL", "YSL"]) {
+            // "island", "isle", "carlisle"
+            i + 1
+        } else if i == 0 && self.matches(i, &["SUGAR"]) {
+            self.add("X", "S");
+            i + 1
+        } else if self.matches(i, &["SH"]) {
+            // Germanic "holm", "holz".
+            if self.matches(i + 1, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.add_both("S");
+            } else {
+                self.add_both("X");
+            }
+            i + 2
+        } else if self.matches(i, &["SIO", "SIA"]) || self.matches(i, &["SIAN"]) {
+            if self.slavo_germanic {
+                self.add_both("S");
+            } else {
+                self.add("S", "X");
+            }
+            i + 3
+        } else if (i == 0 && self.matches(i + 1, &["M", "N", "L", "W"])) || self.matches(i + 1, &["Z"]) {
+            // "Smith" matches "Schmidt", "snider" matches "schneider".
+            self.add("S", "X");
+            if self.matches(i + 1, &["Z"]) {
+                i + 2
+            } else {
+                i + 1
+            }
+        } else if self.matches(i, &["SC"]) {
+            self.handle_sc(i)
+        } else {
+            // French "resnais", "artois".
+            if i == self.len() - 1 && self.matches(i - 2, &["AI", "OI"]) {
+                self.add("", "S");
+            } else {
+                self.add_both("S");
+            }
+
+            if self.matches(i + 1, &["S", "Z"]) {
+                i + 2
+            } else {
+                i + 1
+            }
+        }
+    }
+
+    fn handle_sc(&mut self, i: isize) -> isize {
+        if self.at(i + 2) == b'H' {
+            // Schlesinger's rule.
+            if self.matches(i + 3, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                // Dutch "school", "schermerhorn".
+                if self.matches(i + 3, &["ER", "EN"]) {
+                    self.add("X", "SK");
+                } else {
+                    self.add_both("SK");
+                }
+            } else if i == 0 && !self.is_vowel(3) && self.at(3) != b'W' {
+                self.add("X", "S");
+            } else {
+                self.add_both("X");
+            }
+        } else if self.matches(i + 2, &["I", "E", "Y"]) {
+            self.add_both("S");
+        } else {
+            self.add_both("SK");
+        }
+
+        i + 3
+    }
+
+    fn handle_t(&mut self, i: isize) -> isize {
+        if self.matches(i, &["TION"]) || self.matches(i, &["TIA", "TCH"]) {
+            self.add_both("X");
+            i + 3
+        } else if self.matches(i, &["TH"]) || self.matches(i, &["TTH"]) {
+            // "thomas", "thames" or Germanic.
+            if self.matches(i + 2, &["OM", "AM"]) || self.is_germanic() {
+                self.add_both("T");
+            } else {
+                self.add("0", "T");
+            }
+            i + 2
+        } else {
+            self.add_both("T");
+            if self.matches(i + 1, &["T", "D"]) {
+                i + 2
+            } else {
+                i + 1
+            }
+        }
+    }
+
+    fn handle_w(&mut self, i: isize) -> isize {
+        if self.matches(i, &["WR"]) {
+            self.add_both("R");
+            return i + 2;
+        }
+
+        if i == 0 && (self.is_vowel(i + 1) || self.matches(i, &["WH"])) {
+            // "Wasserman" matches "Vasserman", "Uomo" matches "Womo".
+            if self.is_vowel(i + 1) {
+                self.add("A", "F");
+            } else {
+                self.add_both("A");
+            }
+            i + 1
+        } else if (i == self.len() - 1 && self.is_vowel(i - 1))
+            || self.matches(i - 1, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.matches(0, &["SCH"])
+        {
+            // "Arnow" matches "Arnoff".
+            self.add("", "F");
+            i + 1
+        } else if self.matches(i, &["WICZ", "WITZ"]) {
+            // Polish "filipowicz".
+            self.add("TS", "FX");
+            i + 4
+        } else {
+            i + 1
+        }
+    }
+
+    fn handle_x(&mut self, i: isize) -> isize {
+        if i == 0 {
+            self.add_both("S");
+            return i + 1;
+        }
+
+        // French "breaux".
+        let silent = i == self.len() - 1 && (self.matches(i - 3, &["IAU", "EAU"]) || self.matches(i - 2, &["AU", "OU"]));
+
+        if !silent {
+            self.add_both("KS");
+        }
+
+        if self.matches(i + 1, &["C", "X"]) {
+            i + 2
+        } else {
+            i + 1
+        }
+    }
+
+    fn handle_z(&mut self, i: isize) -> isize {
+        if self.at(i + 1) == b'H' {
+            // Chinese "Zhao".
+            self.add_both("J");
+            return i + 2;
+        }
+
+        if self.matches(i + 1, &["ZO", "ZI", "ZA"]) || (self.slavo_germanic && i > 0 && self.at(i - 1) != b'T') {
+            self.add("S", "TS");
+        } else {
+            self.add_both("S");
+        }
+
+        self.skip_double(i)
+    }
+}
+
+/// Return the primary and alternate Double Metaphone keys of the name, each
+/// at most four chars long. `0` stands for "th" and `X` for "sh" and "ch".
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::double_metaphone;
+///
+/// assert_eq!(double_metaphone("Smith"), ("SM0".to_string(), "XMT".to_string()));
+/// assert_eq!(double_metaphone("Schmidt"), ("XMT".to_string(), "SMT".to_string()));
+/// assert_eq!(double_metaphone("Thompson"), ("TMPS".to_string(), "TMPS".to_string()));
+/// ```
+pub fn double_metaphone(name: &str) -> (String, String) {
+    DoubleMetaphone::new(name).encode()
+}
+
+/// Group names that sound alike, i.e. have the same phonetic key. Groups
+/// and the names in them keep the order of first appearance.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{group_by_phonetic_key, PhoneticAlgorithm};
+///
+/// let names = ["Smith", "Johnson", "Smyth", "Jonson", "Schmidt"];
+///
+/// let answer = group_by_phonetic_key(&names, PhoneticAlgorithm::Soundex);
+/// assert_eq!(answer, vec![vec!["Smith", "Smyth", "Schmidt"], vec!["Johnson", "Jonson"]]);
+///
+/// let answer = group_by_phonetic_key(&names, PhoneticAlgorithm::Nysiis);
+/// assert_eq!(answer, vec![vec!["Smith"], vec!["Johnson", "Jonson"], vec!["Smyth"], vec!["Schmidt"]]);
+/// ```
+pub fn group_by_phonetic_key<'a>(names: &[&'a str], algorithm: PhoneticAlgorithm) -> Vec<Vec<&'a str>> {
+    let mut groups: Vec<Vec<&'a str>> = vec![];
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for &name in names {
+        let idx = *index.entry(algorithm.encode(name)).or_insert_with(|| {
+            groups.push(vec![]);
+            groups.len() - 1
+        });
+
+        groups[idx].push(name);
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soundex() {
+        assert_eq!(soundex(""), "".to_string());
+        assert_eq!(soundex("Tymczak"), "T522".to_string());
+        assert_eq!(soundex("Pfister"), "P236".to_string());
+        assert_eq!(soundex("Honeyman"), "H555".to_string());
+        assert_eq!(soundex("O'Hara"), "O600".to_string());
+        assert_eq!(soundex("Müller"), soundex("Mueller"));
+    }
+
+    #[test]
+    fn test_refined_soundex() {
+        assert_eq!(refined_soundex(""), "".to_string());
+        assert_eq!(refined_soundex("Hailey"), "H070".to_string());
+        assert_eq!(refined_soundex("Smith"), refined_soundex("Smyth"));
+    }
+
+    #[test]
+    fn test_nysiis() {
+        assert_eq!(nysiis(""), "".to_string());
+        assert_eq!(nysiis("Smith"), "SNAT".to_string());
+        assert_eq!(nysiis("Phillipson"), "FALAPS".to_string());
+        assert_eq!(nysiis("Brian"), "BRAN".to_string());
+        assert_eq!(nysiis("Brown"), "BRAN".to_string());
+        assert_eq!(nysiis("Lawrence"), "LARANC".to_string());
+    }
+
+    #[test]
+    fn test_double_metaphone() {
+        let dm = |name| double_metaphone(name);
+
+        assert_eq!(dm(""), ("".to_string(), "".to_string()));
+        assert_eq!(dm("Xavier"), ("SF".to_string(), "SFR".to_string()));
+        assert_eq!(dm("Michael"), ("MKL".to_string(), "MXL".to_string()));
+        assert_eq!(dm("Knight"), ("NT".to_string(), "NT".to_string()));
+        assert_eq!(dm("Caesar"), ("SSR".to_string(), "SSR".to_string()));
+        assert_eq!(dm("Wasserman"), ("ASRM".to_string(), "FSRM".to_string()));
+        assert_eq!(dm("Jose"), ("HS".to_string(), "HS".to_string()));
+        assert_eq!(dm("Filipowicz"), ("FLPT".to_string(), "FLPF".to_string()));
+        assert_eq!(dm("Édouard").0, dm("Edouard").0);
+    }
+}
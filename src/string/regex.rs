@@ -0,0 +1,983 @@
+use std::fmt;
+
+/// Largest bound accepted by a counted repetition such as `a{2,5}`.
+const MAX_REPEAT: u32 = 1000;
+
+/// Largest number of instructions a pattern may compile to. Counted
+/// repetitions are expanded by copying, so nested counts multiply.
+const MAX_PROGRAM: usize = 100_000;
+
+/// Deepest nesting of groups, alternations and quantifiers a pattern may
+/// have, so that parsing and compiling don't overflow the stack.
+const MAX_DEPTH: usize = 256;
+
+/// The reason a pattern was rejected by [`Regex::new`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegexErrorKind {
+    /// A group flag other than `(?:`.
+    InvalidGroup,
+    /// An escape sequence that the engine does not know, e.g. `\q`.
+    InvalidEscape,
+    /// A class range whose start is after its end, e.g. `[z-a]`.
+    InvalidRange,
+    /// A malformed `{m,n}` quantifier or one with `m > n`.
+    InvalidRepeat,
+    /// A quantifier with nothing in front of it, e.g. `*a`.
+    NothingToRepeat,
+    /// A counted repetition larger than the supported maximum.
+    RepeatTooLarge,
+    /// A pattern that compiles to more instructions than supported, e.g.
+    /// `(a{1000}){1000}`.
+    PatternTooLarge,
+    /// A pattern nested deeper than supported, e.g. 300 nested groups.
+    TooDeep,
+    /// A `[` without the matching `]`.
+    UnclosedClass,
+    /// A `(` without the matching `)`.
+    UnclosedGroup,
+    /// A `)` without the matching `(`.
+    UnopenedGroup,
+    /// The pattern ended in the middle of an escape.
+    UnexpectedEnd,
+}
+
+/// An error produced while compiling a pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegexError {
+    /// Byte offset in the pattern where the problem was found.
+    pub position: usize,
+    pub kind: RegexErrorKind,
+}
+
+impl fmt::Display for RegexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self.kind {
+            RegexErrorKind::InvalidGroup => "invalid group flag",
+            RegexErrorKind::InvalidEscape => "invalid escape sequence",
+            RegexErrorKind::InvalidRange => "invalid class range",
+            RegexErrorKind::InvalidRepeat => "invalid repetition",
+            RegexErrorKind::NothingToRepeat => "nothing to repeat",
+            RegexErrorKind::RepeatTooLarge => "repetition bound is too large",
+            RegexErrorKind::PatternTooLarge => "compiled pattern is too large",
+            RegexErrorKind::TooDeep => "pattern is nested too deeply",
+            RegexErrorKind::UnclosedClass => "unclosed character class",
+            RegexErrorKind::UnclosedGroup => "unclosed group",
+            RegexErrorKind::UnopenedGroup => "unopened group",
+            RegexErrorKind::UnexpectedEnd => "unexpected end of pattern",
+        };
+
+        write!(f, "{} at position {}", message, self.position)
+    }
+}
+
+impl std::error::Error for RegexError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Perl {
+    Digit,
+    Word,
+    Space,
+}
+
+impl Perl {
+    fn matches(self, c: char) -> bool {
+        match self {
+            Perl::Digit => c.is_ascii_digit(),
+            Perl::Word => is_word_char(c),
+            Perl::Space => c.is_whitespace(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ClassItem {
+    Range(char, char),
+    Perl(Perl, bool),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Class {
+    items: Vec<ClassItem>,
+    negated: bool,
+}
+
+impl Class {
+    fn matches(&self, c: char) -> bool {
+        let found = self.items.iter().any(|item| match *item {
+            ClassItem::Range(lo, hi) => lo <= c && c <= hi,
+            ClassItem::Perl(perl, negated) => perl.matches(c) != negated,
+        });
+
+        found != self.negated
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Empty,
+    Char(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    WordBoundary(bool),
+    Group(Box<Node>, Option<usize>),
+    Concat(Vec<Node>),
+    Alternate(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: u32,
+        max: Option<u32>,
+        greedy: bool,
+    },
+}
+
+/// A parsed node with the number of instructions it compiles to, saturating
+/// instead of overflowing, and the height of its tree.
+struct Parsed {
+    node: Node,
+    size: usize,
+    height: usize,
+}
+
+impl Parsed {
+    fn leaf(node: Node) -> Parsed {
+        let size = if matches!(node, Node::Empty) { 0 } else { 1 };
+
+        Parsed { node, size, height: 1 }
+    }
+
+    /// Combine the parts into a node with `extra` instructions of its own.
+    fn join(parts: Vec<Parsed>, extra: usize, make: fn(Vec<Node>) -> Node) -> Parsed {
+        let size = parts.iter().fold(extra, |size, part| size.saturating_add(part.size));
+        let height = parts.iter().map(|part| part.height).max().unwrap_or(0) + 1;
+
+        Parsed { node: make(parts.into_iter().map(|part| part.node).collect()), size, height }
+    }
+}
+
+struct Parser {
+    chars: Vec<(usize, char)>,
+    len: usize,
+    idx: usize,
+    groups: usize,
+    // The number of groups open at `idx`.
+    depth: usize,
+}
+
+impl Parser {
+    fn new(pattern: &str) -> Self {
+        Parser {
+            chars: pattern.char_indices().collect(),
+            len: pattern.len(),
+            idx: 0,
+            groups: 0,
+            depth: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.idx).map(|&(_, c)| c)
+    }
+
+    fn position(&self) -> usize {
+        self.chars.get(self.idx).map_or(self.len, |&(p, _)| p)
+    }
+
+    fn error(&self, kind: RegexErrorKind) -> RegexError {
+        RegexError { position: self.position(), kind }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.idx += 1;
+        c
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.idx += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse(mut self) -> Result<(Parsed, usize), RegexError> {
+        let parsed = self.parse_alternate()?;
+
+        if self.peek() == Some(')') {
+            return Err(self.error(RegexErrorKind::UnopenedGroup));
+        }
+
+        Ok((parsed, self.groups))
+    }
+
+    fn parse_alternate(&mut self) -> Result<Parsed, RegexError> {
+        let mut branches = vec![self.parse_concat()?];
+
+        while self.eat('|') {
+            branches.push(self.parse_concat()?);
+        }
+
+        if branches.len() == 1 {
+            Ok(branches.pop().unwrap())
+        } else {
+            let splits = 2 * (branches.len() - 1);
+            Ok(Parsed::join(branches, splits, Node::Alternate))
+        }
+    }
+
+    fn parse_concat(&mut self) -> Result<Parsed, RegexError> {
+        let mut nodes = vec![];
+
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+
+            let atom = self.parse_atom()?;
+            nodes.push(self.parse_quantifiers(atom)?);
+        }
+
+        match nodes.len() {
+            0 => Ok(Parsed::leaf(Node::Empty)),
+            1 => Ok(nodes.pop().unwrap()),
+            _ => Ok(Parsed::join(nodes, 0, Node::Concat)),
+        }
+    }
+
+    fn parse_quantifiers(&mut self, mut parsed: Parsed) -> Result<Parsed, RegexError> {
+        loop {
+            let start = self.idx;
+            let (min, max) = match self.peek() {
+                Some('*') => (0, None),
+                Some('+') => (1, None),
+                Some('?') => (0, Some(1)),
+                Some('{') => {
+                    self.idx += 1;
+                    self.parse_counted(start)?
+                }
+                _ => return Ok(parsed),
+            };
+
+            if self.idx == start {
+                self.idx += 1;
+            }
+
+            let greedy = !self.eat('?');
+            let optional = match max {
+                None => parsed.size.saturating_add(2),
+                Some(max) => ((max - min) as usize).saturating_mul(parsed.size.saturating_add(1)),
+            };
+            let size = (min as usize).saturating_mul(parsed.size).saturating_add(optional);
+            let height = parsed.height + 1;
+
+            if size > MAX_PROGRAM {
+                return Err(RegexError { position: self.chars[start].0, kind: RegexErrorKind::PatternTooLarge });
+            }
+
+            if height > MAX_DEPTH {
+                return Err(RegexError { position: self.chars[start].0, kind: RegexErrorKind::TooDeep });
+            }
+
+            let node = Node::Repeat { node: Box::new(parsed.node), min, max, greedy };
+            parsed = Parsed { node, size, height };
+        }
+    }
+
+    fn parse_counted(&mut self, start: usize) -> Result<(u32, Option<u32>), RegexError> {
+        let invalid = |p: &Parser| RegexError {
+            position: p.chars[start].0,
+            kind: RegexErrorKind::InvalidRepeat,
+        };
+
+        let min = self.parse_number().ok_or_else(|| invalid(self))?;
+        let max = if self.eat(',') {
+            if self.peek() == Some('}') {
+                None
+            } else {
+                Some(self.parse_number().ok_or_else(|| invalid(self))?)
+            }
+        } else {
+            Some(min)
+        };
+
+        if !self.eat('}') || max.is_some_and(|max| max < min) {
+            return Err(invalid(self));
+        }
+
+        if min > MAX_REPEAT || max.is_some_and(|max| max > MAX_REPEAT) {
+            return Err(RegexError {
+                position: self.chars[start].0,
+                kind: RegexErrorKind::RepeatTooLarge,
+            });
+        }
+
+        Ok((min, max))
+    }
+
+    fn parse_number(&mut self) -> Option<u32> {
+        let mut value: Option<u32> = None;
+
+        while let Some(d) = self.peek().and_then(|c| c.to_digit(10)) {
+            self.idx += 1;
+            value = Some(value.unwrap_or(0).saturating_mul(10).saturating_add(d));
+        }
+
+        value
+    }
+
+    fn parse_atom(&mut self) -> Result<Parsed, RegexError> {
+        let position = self.position();
+
+        let node = match self.bump().unwrap() {
+            '(' => {
+                self.depth += 1;
+
+                if self.depth > MAX_DEPTH {
+                    return Err(RegexError { position, kind: RegexErrorKind::TooDeep });
+                }
+
+                let index = if self.eat('?') {
+                    if !self.eat(':') {
+                        return Err(RegexError { position, kind: RegexErrorKind::InvalidGroup });
+                    }
+                    None
+                } else {
+                    self.groups += 1;
+                    Some(self.groups)
+                };
+
+                let inner = self.parse_alternate()?;
+
+                if !self.eat(')') {
+                    return Err(RegexError { position, kind: RegexErrorKind::UnclosedGroup });
+                }
+
+                self.depth -= 1;
+
+                if inner.height >= MAX_DEPTH {
+                    return Err(RegexError { position, kind: RegexErrorKind::TooDeep });
+                }
+
+                let saves = if index.is_some() { 2 } else { 0 };
+                let node = Node::Group(Box::new(inner.node), index);
+
+                return Ok(Parsed { node, size: inner.size.saturating_add(saves), height: inner.height + 1 });
+            }
+            '[' => self.parse_class(position)?,
+            '.' => Node::Any,
+            '^' => Node::Start,
+            '$' => Node::End,
+            '*' | '+' | '?' | '{' => return Err(RegexError { position, kind: RegexErrorKind::NothingToRepeat }),
+            '\\' if self.eat('b') => Node::WordBoundary(true),
+            '\\' if self.eat('B') => Node::WordBoundary(false),
+            '\\' => match self.parse_escape(position)? {
+                ClassItem::Range(c, _) => Node::Char(c),
+                ClassItem::Perl(perl, negated) => Node::Class(Class {
+                    items: vec![ClassItem::Perl(perl, negated)],
+                    negated: false,
+                }),
+            },
+            c => Node::Char(c),
+        };
+
+        Ok(Parsed::leaf(node))
+    }
+
+    fn parse_escape(&mut self, position: usize) -> Result<ClassItem, RegexError> {
+        let c = self.bump().ok_or(RegexError { position, kind: RegexErrorKind::UnexpectedEnd })?;
+
+        let item = match c {
+            'd' => ClassItem::Perl(Perl::Digit, false),
+            'D' => ClassItem::Perl(Perl::Digit, true),
+            'w' => ClassItem::Perl(Perl::Word, false),
+            'W' => ClassItem::Perl(Perl::Word, true),
+            's' => ClassItem::Perl(Perl::Space, false),
+            'S' => ClassItem::Perl(Perl::Space, true),
+            'n' => ClassItem::Range('\n', '\n'),
+            'r' => ClassItem::Range('\r', '\r'),
+            't' => ClassItem::Range('\t', '\t'),
+            '0' => ClassItem::Range('\0', '\0'),
+            c if c.is_ascii_punctuation() => ClassItem::Range(c, c),
+            _ => return Err(RegexError { position, kind: RegexErrorKind::InvalidEscape }),
+        };
+
+        Ok(item)
+    }
+
+    fn parse_class(&mut self, position: usize) -> Result<Node, RegexError> {
+        let unclosed = RegexError { position, kind: RegexErrorKind::UnclosedClass };
+        let negated = self.eat('^');
+        let mut items = vec![];
+        let mut first = true;
+
+        loop {
+            let item_position = self.position();
+            let c = self.bump().ok_or(unclosed)?;
+
+            if c == ']' && !first {
+                break;
+            }
+            first = false;
+
+            let lo = match c {
+                '\\' => match self.parse_escape(item_position)? {
+                    ClassItem::Range(lo, _) => lo,
+                    perl => {
+                        items.push(perl);
+                        continue;
+                    }
+                },
+                c => c,
+            };
+
+            let is_range = self.peek() == Some('-')
+                && self.chars.get(self.idx + 1).is_some_and(|&(_, c)| c != ']');
+
+            if !is_range {
+                items.push(ClassItem::Range(lo, lo));
+                continue;
+            }
+
+            self.idx += 1;
+            let hi_position = self.position();
+            let hi = match self.bump().ok_or(unclosed)? {
+                '\\' => match self.parse_escape(hi_position)? {
+                    ClassItem::Range(hi, _) => hi,
+                    _ => return Err(RegexError { position: hi_position, kind: RegexErrorKind::InvalidRange }),
+                },
+                c => c,
+            };
+
+            if lo > hi {
+                return Err(RegexError { position: item_position, kind: RegexErrorKind::InvalidRange });
+            }
+
+            items.push(ClassItem::Range(lo, hi));
+        }
+
+        Ok(Node::Class(Class { items, negated }))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    WordBoundary(bool),
+    Save(usize),
+    Split(usize, usize),
+    Jmp(usize),
+    Match,
+}
+
+struct Compiler {
+    program: Vec<Inst>,
+}
+
+impl Compiler {
+    fn push(&mut self, inst: Inst) -> usize {
+        self.program.push(inst);
+        self.program.len() - 1
+    }
+
+    fn split(&mut self, at: usize, body: usize, out: usize, greedy: bool) {
+        self.program[at] = if greedy {
+            Inst::Split(body, out)
+        } else {
+            Inst::Split(out, body)
+        };
+    }
+
+    fn emit(&mut self, node: &Node) {
+        match node {
+            Node::Empty => {}
+            Node::Char(c) => {
+                self.push(Inst::Char(*c));
+            }
+            Node::Any => {
+                self.push(Inst::Any);
+            }
+            Node::Class(class) => {
+                self.push(Inst::Class(class.clone()));
+            }
+            Node::Start => {
+                self.push(Inst::Start);
+            }
+            Node::End => {
+                self.push(Inst::End);
+            }
+            Node::WordBoundary(expected) => {
+                self.push(Inst::WordBoundary(*expected));
+            }
+            Node::Group(node, None) => self.emit(node),
+            Node::Group(node, Some(index)) => {
+                self.push(Inst::Save(2 * index));
+                self.emit(node);
+                self.push(Inst::Save(2 * index + 1));
+            }
+            Node::Concat(nodes) => nodes.iter().for_each(|node| self.emit(node)),
+            Node::Alternate(branches) => {
+                let mut jumps = vec![];
+
+                for (i, branch) in branches.iter().enumerate() {
+                    if i + 1 == branches.len() {
+                        self.emit(branch);
+                        break;
+                    }
+
+                    let split = self.push(Inst::Match);
+                    self.emit(branch);
+                    jumps.push(self.push(Inst::Match));
+                    let next = self.program.len();
+                    self.split(split, split + 1, next, true);
+                }
+
+                let out = self.program.len();
+                jumps.into_iter().for_each(|j| self.program[j] = Inst::Jmp(out));
+            }
+            Node::Repeat { node, min, max, greedy } => {
+                for _ in 0..*min {
+                    self.emit(node);
+                }
+
+                match max {
+                    None => {
+                        let split = self.push(Inst::Match);
+                        self.emit(node);
+                        self.push(Inst::Jmp(split));
+                        let out = self.program.len();
+                        self.split(split, split + 1, out, *greedy);
+                    }
+                    Some(max) => {
+                        let holes: Vec<usize> = (*min..*max)
+                            .map(|_| {
+                                let split = self.push(Inst::Match);
+                                self.emit(node);
+                                split
+                            })
+                            .collect();
+                        let out = self.program.len();
+                        holes.into_iter().for_each(|s| self.split(s, s + 1, out, *greedy));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Thread {
+    pc: usize,
+    slots: Vec<Option<usize>>,
+}
+
+struct Threads {
+    list: Vec<Thread>,
+    seen: Vec<bool>,
+}
+
+impl Threads {
+    fn new(size: usize) -> Self {
+        Threads { list: vec![], seen: vec![false; size] }
+    }
+
+    fn clear(&mut self) {
+        self.list.clear();
+        self.seen.iter_mut().for_each(|s| *s = false);
+    }
+}
+
+/// A compiled regular expression.
+///
+/// Patterns are compiled to a Thompson NFA and run on a Pike VM, so matching
+/// always takes `O(pattern * text)` time no matter how the pattern is written.
+/// Matches follow leftmost-first semantics, the same as Perl and most other
+/// backtracking engines, and report byte offsets into the text.
+///
+/// Supported syntax:
+///
+/// * literals, `.` (anything but `\n`) and escapes `\. \* \\ \n \t \r \0`;
+/// * classes `[abc]`, `[^a-z]`, `\d \w \s` and their negations `\D \W \S`;
+/// * anchors `^` and `$`, word boundaries `\b` and `\B`;
+/// * repetition `* + ? {m} {m,} {m,n}`, lazy when followed by `?`;
+/// * alternation `|`, capture groups `(...)` and plain groups `(?:...)`.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::Regex;
+///
+/// let re = Regex::new(r"(\w+)@(\w+)\.com").unwrap();
+/// assert_eq!(re.is_match("mail me at kek@lol.com"), true);
+/// assert_eq!(re.find("mail me at kek@lol.com"), Some((11, 22)));
+///
+/// let caps = re.captures("kek@lol.com").unwrap();
+/// assert_eq!(caps, vec![Some((0, 11)), Some((0, 3)), Some((4, 7))]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Regex {
+    program: Vec<Inst>,
+    groups: usize,
+}
+
+impl Regex {
+    /// Compile a pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::{Regex, RegexErrorKind};
+    ///
+    /// let answer = Regex::new("a(b|c").unwrap_err();
+    /// assert_eq!(answer.kind, RegexErrorKind::UnclosedGroup);
+    /// assert_eq!(answer.position, 1);
+    /// ```
+    pub fn new(pattern: &str) -> Result<Regex, RegexError> {
+        let (parsed, groups) = Parser::new(pattern).parse()?;
+
+        if parsed.size > MAX_PROGRAM - 3 {
+            return Err(RegexError { position: 0, kind: RegexErrorKind::PatternTooLarge });
+        }
+
+        let mut compiler = Compiler { program: vec![Inst::Save(0)] };
+
+        compiler.emit(&parsed.node);
+        compiler.push(Inst::Save(1));
+        compiler.push(Inst::Match);
+
+        Ok(Regex { program: compiler.program, groups })
+    }
+
+    /// Return the number of capture groups, counting the implicit group 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::Regex;
+    ///
+    /// let re = Regex::new("(a)(?:b)(c)").unwrap();
+    /// assert_eq!(re.captures_len(), 3);
+    /// ```
+    pub fn captures_len(&self) -> usize {
+        self.groups + 1
+    }
+
+    /// Return whether true or false if the pattern matches somewhere in the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::Regex;
+    ///
+    /// let re = Regex::new("^a+b?$").unwrap();
+    /// assert_eq!(re.is_match("aaab"), true);
+    /// assert_eq!(re.is_match("aaabb"), false);
+    /// ```
+    pub fn is_match(&self, text: &str) -> bool {
+        self.find(text).is_some()
+    }
+
+    /// Return the byte span of the leftmost match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::Regex;
+    ///
+    /// let re = Regex::new(r"\d+").unwrap();
+    /// assert_eq!(re.find("abc 123 45"), Some((4, 7)));
+    /// assert_eq!(re.find("abc"), None);
+    /// ```
+    pub fn find(&self, text: &str) -> Option<(usize, usize)> {
+        self.captures_at(text, 0).and_then(|caps| caps[0])
+    }
+
+    /// Return the byte spans of all successive non-overlapping matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::Regex;
+    ///
+    /// let re = Regex::new(r"\d+").unwrap();
+    /// assert_eq!(re.find_all("1 22 333"), vec![(0, 1), (2, 4), (5, 8)]);
+    /// ```
+    pub fn find_all(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut result = vec![];
+        let mut start = 0;
+
+        while start <= text.len() {
+            let Some((from, to)) = self.captures_at(text, start).and_then(|caps| caps[0]) else {
+                break;
+            };
+
+            result.push((from, to));
+
+            start = if to > from {
+                to
+            } else {
+                match text[to..].chars().next() {
+                    Some(c) => to + c.len_utf8(),
+                    None => break,
+                }
+            };
+        }
+
+        result
+    }
+
+    /// Return the byte spans of every capture group of the leftmost match.
+    /// Index 0 is the whole match, groups that did not take part are `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::Regex;
+    ///
+    /// let re = Regex::new("(a)|(b)").unwrap();
+    /// assert_eq!(re.captures("b"), Some(vec![Some((0, 1)), None, Some((0, 1))]));
+    /// assert_eq!(re.captures("c"), None);
+    /// ```
+    pub fn captures(&self, text: &str) -> Option<Vec<Option<(usize, usize)>>> {
+        self.captures_at(text, 0)
+    }
+
+    fn captures_at(&self, text: &str, start: usize) -> Option<Vec<Option<(usize, usize)>>> {
+        let slots = self.pike(text, start)?;
+
+        Some(
+            slots
+                .chunks(2)
+                .map(|pair| match (pair[0], pair[1]) {
+                    (Some(from), Some(to)) => Some((from, to)),
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+
+    fn pike(&self, text: &str, start: usize) -> Option<Vec<Option<usize>>> {
+        let mut current = Threads::new(self.program.len());
+        let mut next = Threads::new(self.program.len());
+        let mut slots = vec![None; 2 * (self.groups + 1)];
+        let mut matched = None;
+        let mut pos = start;
+
+        loop {
+            let prev = text[..pos].chars().next_back();
+            let c = text[pos..].chars().next();
+
+            if matched.is_none() {
+                self.add(&mut current, 0, text, pos, prev, c, &mut slots);
+            }
+
+            if current.list.is_empty() && matched.is_some() {
+                break;
+            }
+
+            let next_pos = pos + c.map_or(0, char::len_utf8);
+
+            for i in 0..current.list.len() {
+                let pc = current.list[i].pc;
+                let step = match (&self.program[pc], c) {
+                    (Inst::Match, _) => {
+                        matched = Some(current.list[i].slots.clone());
+                        break;
+                    }
+                    (Inst::Char(x), Some(c)) => *x == c,
+                    (Inst::Any, Some(c)) => c != '\n',
+                    (Inst::Class(class), Some(c)) => class.matches(c),
+                    _ => false,
+                };
+
+                if step {
+                    let mut thread_slots = std::mem::take(&mut current.list[i].slots);
+                    self.add(&mut next, pc + 1, text, next_pos, c, text[next_pos..].chars().next(), &mut thread_slots);
+                }
+            }
+
+            std::mem::swap(&mut current, &mut next);
+            next.clear();
+
+            if c.is_none() {
+                break;
+            }
+            pos = next_pos;
+        }
+
+        matched
+    }
+
+    /// Add the thread at `pc` and every thread reachable from it without
+    /// consuming a char, in priority order. An explicit stack keeps long
+    /// chains of splits from overflowing the call stack.
+    #[allow(clippy::too_many_arguments)]
+    fn add(
+        &self,
+        threads: &mut Threads,
+        pc: usize,
+        text: &str,
+        pos: usize,
+        prev: Option<char>,
+        next: Option<char>,
+        slots: &mut [Option<usize>],
+    ) {
+        let mut stack = vec![Frame::Explore(pc)];
+
+        while let Some(frame) = stack.pop() {
+            let pc = match frame {
+                Frame::Explore(pc) => pc,
+                Frame::Restore(slot, old) => {
+                    slots[slot] = old;
+                    continue;
+                }
+            };
+
+            if threads.seen[pc] {
+                continue;
+            }
+            threads.seen[pc] = true;
+
+            match self.program[pc] {
+                Inst::Jmp(to) => stack.push(Frame::Explore(to)),
+                Inst::Split(x, y) => {
+                    stack.push(Frame::Explore(y));
+                    stack.push(Frame::Explore(x));
+                }
+                Inst::Save(slot) => {
+                    let old = slots[slot].replace(pos);
+                    stack.push(Frame::Restore(slot, old));
+                    stack.push(Frame::Explore(pc + 1));
+                }
+                Inst::Start => {
+                    if pos == 0 {
+                        stack.push(Frame::Explore(pc + 1));
+                    }
+                }
+                Inst::End => {
+                    if pos == text.len() {
+                        stack.push(Frame::Explore(pc + 1));
+                    }
+                }
+                Inst::WordBoundary(expected) => {
+                    let is_boundary = prev.is_some_and(is_word_char) != next.is_some_and(is_word_char);
+
+                    if is_boundary == expected {
+                        stack.push(Frame::Explore(pc + 1));
+                    }
+                }
+                _ => threads.list.push(Thread { pc, slots: slots.to_vec() }),
+            }
+        }
+    }
+}
+
+/// A pending step of [`Regex::add`].
+enum Frame {
+    Explore(usize),
+    /// Put back a slot value once everything after its `Save` is explored.
+    Restore(usize, Option<usize>),
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_match() {
+        let re = Regex::new("^(a|b)*c{2,3}$").unwrap();
+        assert_eq!(re.is_match("ababcc"), true);
+        assert_eq!(re.is_match("ccc"), true);
+        assert_eq!(re.is_match("abcccc"), false);
+
+        let re = Regex::new(r"[^\d\s]+").unwrap();
+        assert_eq!(re.find("12 abc 3"), Some((3, 6)));
+    }
+
+    #[test]
+    fn test_find_all() {
+        let re = Regex::new(r"\bмир\b").unwrap();
+        assert_eq!(re.find_all("мир, мирный мир"), vec![(0, 6), (21, 27)]);
+
+        let re = Regex::new("a*").unwrap();
+        assert_eq!(re.find_all("baa"), vec![(0, 0), (1, 3), (3, 3)]);
+    }
+
+    #[test]
+    fn test_captures() {
+        let re = Regex::new("(a+?)(a*)").unwrap();
+        assert_eq!(re.captures("aaa"), Some(vec![Some((0, 3)), Some((0, 1)), Some((1, 3))]));
+
+        let re = Regex::new("(a*)*b").unwrap();
+        assert_eq!(re.find(&"a".repeat(64)), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        let answer = Regex::new("*a").unwrap_err();
+        assert_eq!(answer, RegexError { position: 0, kind: RegexErrorKind::NothingToRepeat });
+
+        let answer = Regex::new("[z-a]").unwrap_err();
+        assert_eq!(answer.kind, RegexErrorKind::InvalidRange);
+
+        let answer = Regex::new("a)").unwrap_err();
+        assert_eq!(answer, RegexError { position: 1, kind: RegexErrorKind::UnopenedGroup });
+
+        let answer = Regex::new("a{3,1}").unwrap_err();
+        assert_eq!(answer.kind, RegexErrorKind::InvalidRepeat);
+    }
+
+    #[test]
+    fn test_pattern_too_large() {
+        let answer = Regex::new("((a{1000}){1000}){1000}").unwrap_err();
+        assert_eq!(answer, RegexError { position: 10, kind: RegexErrorKind::PatternTooLarge });
+
+        let answer = Regex::new("(?:(?:(?:a{1000}){1000}){10})").unwrap_err();
+        assert_eq!(answer.kind, RegexErrorKind::PatternTooLarge);
+
+        let answer = Regex::new(&"a{1000}".repeat(101)).unwrap_err();
+        assert_eq!(answer, RegexError { position: 0, kind: RegexErrorKind::PatternTooLarge });
+
+        assert_eq!(Regex::new("(?:a{1000}){99}").is_ok(), true);
+    }
+
+    #[test]
+    fn test_too_deep() {
+        let nested = |n: usize| "(".repeat(n) + "a" + &")".repeat(n);
+
+        assert_eq!(Regex::new(&nested(255)).unwrap().find("xa"), Some((1, 2)));
+
+        let answer = Regex::new(&nested(256)).unwrap_err();
+        assert_eq!(answer, RegexError { position: 0, kind: RegexErrorKind::TooDeep });
+
+        let answer = Regex::new(&("(".repeat(20000) + &")".repeat(20000))).unwrap_err();
+        assert_eq!(answer, RegexError { position: 256, kind: RegexErrorKind::TooDeep });
+
+        let answer = Regex::new(&("a".to_string() + &"*".repeat(20000))).unwrap_err();
+        assert_eq!(answer, RegexError { position: 256, kind: RegexErrorKind::TooDeep });
+
+        let answer = Regex::new(&("(?:a".to_string() + &"*".repeat(200) + ")" + &"*".repeat(200))).unwrap_err();
+        assert_eq!(answer.kind, RegexErrorKind::TooDeep);
+    }
+
+    #[test]
+    fn test_long_split_chain() {
+        let pattern: Vec<String> = (0..10000).map(|i| format!("x{}y", i)).collect();
+        let re = Regex::new(&pattern.join("|")).unwrap();
+        assert_eq!(re.find("-- x9999y --"), Some((3, 9)));
+
+        let re = Regex::new(&"a?".repeat(20000)).unwrap();
+        assert_eq!(re.find("aaa"), Some((0, 3)));
+    }
+}
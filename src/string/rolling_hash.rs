@@ -0,0 +1,276 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::ops::Range;
+
+/// The Mersenne prime 2^61 - 1 used as the modulus.
+const MODULUS: u64 = (1 << 61) - 1;
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    let product = a as u128 * b as u128;
+    let reduced = (product & MODULUS as u128) as u64 + (product >> 61) as u64;
+
+    if reduced >= MODULUS {
+        reduced - MODULUS
+    } else {
+        reduced
+    }
+}
+
+fn add_mod(a: u64, b: u64) -> u64 {
+    let sum = a + b;
+
+    if sum >= MODULUS {
+        sum - MODULUS
+    } else {
+        sum
+    }
+}
+
+fn char_value(c: char) -> u64 {
+    c as u64 + 1
+}
+
+/// Polynomial rolling hash of a string modulo 2^61 - 1.
+///
+/// After `O(n)` preprocessing the hash of any substring is available in
+/// `O(1)`. Positions are char indices, not byte offsets. The base is chosen at
+/// random for every instance, so equal hashes mean equal substrings with
+/// overwhelming probability; the search methods additionally verify matches.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::RollingHash;
+///
+/// let rh = RollingHash::new("abcabc");
+/// assert_eq!(rh.substring_hash(0..3), rh.substring_hash(3..6));
+/// assert_eq!(rh.substrings_equal(0..2, 3..5), true);
+/// assert_eq!(rh.substrings_equal(0..2, 1..3), false);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingHash {
+    chars: Vec<char>,
+    base: u64,
+    prefix: Vec<u64>,
+    powers: Vec<u64>,
+}
+
+impl RollingHash {
+    pub fn new(text: &str) -> Self {
+        let random = RandomState::new().build_hasher().finish();
+
+        RollingHash::with_base(text, random % (MODULUS - 1024) + 1024)
+    }
+
+    /// Build the hash with a fixed base, for reproducible hash values.
+    /// The base is reduced modulo 2^61 - 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::RollingHash;
+    ///
+    /// let rh = RollingHash::with_base("ab", 1000);
+    /// assert_eq!(rh.substring_hash(0..2), 98 * 1000 + 99);
+    /// ```
+    pub fn with_base(text: &str, base: u64) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        let base = base % MODULUS;
+        let mut prefix = vec![0; chars.len() + 1];
+        let mut powers = vec![1; chars.len() + 1];
+
+        for (i, &c) in chars.iter().enumerate() {
+            prefix[i + 1] = add_mod(mul_mod(prefix[i], base), char_value(c));
+            powers[i + 1] = mul_mod(powers[i], base);
+        }
+
+        RollingHash { chars, base, prefix, powers }
+    }
+
+    /// Return the number of chars in the text.
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    /// Return whether true or false if the text is empty.
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    /// Return the hash of the chars in the range.
+    pub fn substring_hash(&self, range: Range<usize>) -> u64 {
+        let removed = mul_mod(self.prefix[range.start], self.powers[range.len()]);
+
+        add_mod(self.prefix[range.end], MODULUS - removed)
+    }
+
+    /// Return the hash of another string, comparable with `substring_hash`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::RollingHash;
+    ///
+    /// let rh = RollingHash::new("привет мир");
+    /// assert_eq!(rh.hash_str("мир"), rh.substring_hash(7..10));
+    /// ```
+    pub fn hash_str(&self, s: &str) -> u64 {
+        self.hash_chars(s.chars())
+    }
+
+    fn hash_chars<I: IntoIterator<Item = char>>(&self, chars: I) -> u64 {
+        chars.into_iter().fold(0, |h, c| add_mod(mul_mod(h, self.base), char_value(c)))
+    }
+
+    /// Return whether true or false if the two ranges hold equal substrings,
+    /// comparing hashes in `O(1)`.
+    pub fn substrings_equal(&self, a: Range<usize>, b: Range<usize>) -> bool {
+        a.len() == b.len() && self.substring_hash(a) == self.substring_hash(b)
+    }
+
+    /// Return the char positions of every occurrence of the pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::RollingHash;
+    ///
+    /// let rh = RollingHash::new("abababa");
+    /// assert_eq!(rh.find_all("aba"), vec![0, 2, 4]);
+    /// ```
+    pub fn find_all(&self, pattern: &str) -> Vec<usize> {
+        self.find_all_multi(&[pattern]).into_iter().map(|(_, pos)| pos).collect()
+    }
+
+    /// Return every occurrence of every pattern as `(pattern index, char position)`,
+    /// ordered by position and then by pattern index. Patterns of each length
+    /// are found in one pass over the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::RollingHash;
+    ///
+    /// let rh = RollingHash::new("she sells shells");
+    /// let answer = rh.find_all_multi(&["she", "ells", "s"]);
+    /// assert_eq!(answer, vec![(0, 0), (2, 0), (2, 4), (1, 5), (2, 8), (0, 10), (2, 10), (1, 12), (2, 15)]);
+    /// ```
+    pub fn find_all_multi(&self, patterns: &[&str]) -> Vec<(usize, usize)> {
+        let mut by_len: HashMap<usize, HashMap<u64, Vec<usize>>> = HashMap::new();
+        let patterns: Vec<Vec<char>> = patterns.iter().map(|p| p.chars().collect()).collect();
+
+        for (idx, pattern) in patterns.iter().enumerate() {
+            let hash = self.hash_chars(pattern.iter().copied());
+            by_len.entry(pattern.len()).or_default().entry(hash).or_default().push(idx);
+        }
+
+        let mut result = vec![];
+
+        for (len, hashes) in by_len {
+            for start in 0..(self.len() + 1).saturating_sub(len) {
+                let Some(candidates) = hashes.get(&self.substring_hash(start..start + len)) else {
+                    continue;
+                };
+
+                for &idx in candidates {
+                    if self.chars[start..start + len] == patterns[idx][..] {
+                        result.push((idx, start));
+                    }
+                }
+            }
+        }
+
+        result.sort_unstable_by_key(|&(idx, pos)| (pos, idx));
+        result
+    }
+
+    /// Return the first start of a substring of the given length that occurs twice.
+    fn find_duplicate(&self, len: usize) -> Option<usize> {
+        let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+
+        for start in 0..(self.len() + 1).saturating_sub(len) {
+            let bucket = seen.entry(self.substring_hash(start..start + len)).or_default();
+
+            if bucket.iter().any(|&s| self.chars[s..s + len] == self.chars[start..start + len]) {
+                return Some(start);
+            }
+
+            bucket.push(start);
+        }
+
+        None
+    }
+
+    /// Return the longest substring that occurs at least twice, possibly
+    /// overlapping itself, found by binary search on the length.
+    /// Among several of the same length the leftmost second occurrence wins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::RollingHash;
+    ///
+    /// let rh = RollingHash::new("banana");
+    /// assert_eq!(rh.longest_duplicated_substring(), "ana".to_string());
+    ///
+    /// let rh = RollingHash::new("abcd");
+    /// assert_eq!(rh.longest_duplicated_substring(), "".to_string());
+    /// ```
+    pub fn longest_duplicated_substring(&self) -> String {
+        let (mut low, mut high) = (0, self.len());
+        let mut best = None;
+
+        while low < high {
+            let mid = (low + high).div_ceil(2);
+
+            match self.find_duplicate(mid) {
+                Some(start) => {
+                    best = Some((start, mid));
+                    low = mid;
+                }
+                None => high = mid - 1,
+            }
+        }
+
+        best.map_or(String::new(), |(start, len)| self.chars[start..start + len].iter().collect())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substring_hash() {
+        let rh = RollingHash::new("мама мыла раму");
+        assert_eq!(rh.substrings_equal(0..2, 2..4), true);
+        assert_eq!(rh.substrings_equal(1..3, 11..13), true);
+        assert_eq!(rh.substrings_equal(0..2, 5..7), false);
+        assert_eq!(rh.substrings_equal(0..0, 3..3), true);
+        assert_eq!(rh.substring_hash(5..9), rh.hash_str("мыла"));
+    }
+
+    #[test]
+    fn test_find_all_multi() {
+        let rh = RollingHash::new("aaaa");
+        let answer = rh.find_all_multi(&["aa", "a", "aaaaa", "b"]);
+        assert_eq!(answer, vec![(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2), (1, 3)]);
+
+        let answer = rh.find_all("");
+        assert_eq!(answer, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_longest_duplicated_substring() {
+        let rh = RollingHash::new("mississippi");
+        assert_eq!(rh.longest_duplicated_substring(), "issi".to_string());
+
+        let rh = RollingHash::new("aaaaa");
+        assert_eq!(rh.longest_duplicated_substring(), "aaaa".to_string());
+
+        let rh = RollingHash::new("");
+        assert_eq!(rh.longest_duplicated_substring(), "".to_string());
+    }
+}
@@ -0,0 +1,614 @@
+use std::fmt;
+use std::ops::Range;
+
+/// Text that can be read char by char from both ends. It lets the string
+/// algorithms run over `&str`, `String` and [`Rope`] alike.
+pub trait CharSource {
+    type Chars<'a>: DoubleEndedIterator<Item = char>
+    where
+        Self: 'a;
+
+    /// Return an iterator over the chars of the text.
+    fn source_chars(&self) -> Self::Chars<'_>;
+}
+
+impl CharSource for str {
+    type Chars<'a> = std::str::Chars<'a>;
+
+    fn source_chars(&self) -> Self::Chars<'_> {
+        self.chars()
+    }
+}
+
+impl CharSource for String {
+    type Chars<'a> = std::str::Chars<'a>;
+
+    fn source_chars(&self) -> Self::Chars<'_> {
+        self.chars()
+    }
+}
+
+impl<T: CharSource + ?Sized> CharSource for &T {
+    type Chars<'a>
+        = T::Chars<'a>
+    where
+        Self: 'a;
+
+    fn source_chars(&self) -> Self::Chars<'_> {
+        (**self).source_chars()
+    }
+}
+
+/// The maximum number of bytes in a leaf.
+const MAX_LEAF: usize = 512;
+
+#[derive(Debug, Clone)]
+enum Node {
+    Leaf {
+        text: String,
+        chars: usize,
+        newlines: usize,
+    },
+    Branch {
+        left: Box<Node>,
+        right: Box<Node>,
+        chars: usize,
+        newlines: usize,
+        height: usize,
+    },
+}
+
+impl Node {
+    fn leaf(text: &str) -> Node {
+        Node::Leaf { text: text.to_string(), chars: text.chars().count(), newlines: text.matches('\n').count() }
+    }
+
+    fn branch(left: Node, right: Node) -> Node {
+        Node::Branch {
+            chars: left.chars() + right.chars(),
+            newlines: left.newlines() + right.newlines(),
+            height: left.height().max(right.height()) + 1,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    /// Build a balanced tree out of the text cut into leaves.
+    fn build(text: &str) -> Node {
+        if text.len() <= MAX_LEAF {
+            return Node::leaf(text);
+        }
+
+        let mut mid = text.len() / 2;
+        while !text.is_char_boundary(mid) {
+            mid += 1;
+        }
+
+        Node::branch(Node::build(&text[..mid]), Node::build(&text[mid..]))
+    }
+
+    fn chars(&self) -> usize {
+        match self {
+            Node::Leaf { chars, .. } | Node::Branch { chars, .. } => *chars,
+        }
+    }
+
+    fn newlines(&self) -> usize {
+        match self {
+            Node::Leaf { newlines, .. } | Node::Branch { newlines, .. } => *newlines,
+        }
+    }
+
+    fn height(&self) -> usize {
+        match self {
+            Node::Leaf { .. } => 0,
+            Node::Branch { height, .. } => *height,
+        }
+    }
+
+    /// Return the leaf holding the char offset, or the last leaf if it is
+    /// past the end, and the offset where the leaf starts.
+    fn leaf_at(&self, idx: usize) -> (&str, usize) {
+        let mut node = self;
+        let mut start = 0;
+
+        loop {
+            match node {
+                Node::Leaf { text, .. } => return (text, start),
+                Node::Branch { left, right, .. } => {
+                    if idx < start + left.chars() {
+                        node = left;
+                    } else {
+                        start += left.chars();
+                        node = right;
+                    }
+                }
+            }
+        }
+    }
+
+    fn into_children(self) -> (Node, Node) {
+        match self {
+            Node::Branch { left, right, .. } => (*left, *right),
+            Node::Leaf { .. } => unreachable!("a leaf has no children"),
+        }
+    }
+
+    fn rotate_left(self) -> Node {
+        let (left, right) = self.into_children();
+        let (middle, right) = right.into_children();
+
+        Node::branch(Node::branch(left, middle), right)
+    }
+
+    fn rotate_right(self) -> Node {
+        let (left, right) = self.into_children();
+        let (left, middle) = left.into_children();
+
+        Node::branch(left, Node::branch(middle, right))
+    }
+
+    /// Return the heights of the children, or zeros for a leaf.
+    fn child_heights(&self) -> (usize, usize) {
+        match self {
+            Node::Branch { left, right, .. } => (left.height(), right.height()),
+            Node::Leaf { .. } => (0, 0),
+        }
+    }
+
+    /// Restore the AVL balance of a node whose children differ in height by two.
+    fn rebalance(self) -> Node {
+        let (left_height, right_height) = self.child_heights();
+
+        if left_height > right_height + 1 {
+            let (left, right) = self.into_children();
+            let (outer, inner) = left.child_heights();
+            let left = if inner > outer { left.rotate_left() } else { left };
+
+            Node::branch(left, right).rotate_right()
+        } else if right_height > left_height + 1 {
+            let (left, right) = self.into_children();
+            let (inner, outer) = right.child_heights();
+            let right = if inner > outer { right.rotate_right() } else { right };
+
+            Node::branch(left, right).rotate_left()
+        } else {
+            self
+        }
+    }
+
+    /// Concatenate two trees in `O(|height difference|)`.
+    fn join(a: Node, b: Node) -> Node {
+        if a.chars() == 0 {
+            return b;
+        }
+
+        if b.chars() == 0 {
+            return a;
+        }
+
+        match (a, b) {
+            (Node::Leaf { text: mut x, .. }, Node::Leaf { text: y, .. }) if x.len() + y.len() <= MAX_LEAF => {
+                x.push_str(&y);
+                Node::leaf(&x)
+            }
+            (a, b) if a.height() > b.height() + 1 => {
+                let (left, right) = a.into_children();
+                Node::branch(left, Node::join(right, b)).rebalance()
+            }
+            (a, b) if b.height() > a.height() + 1 => {
+                let (left, right) = b.into_children();
+                Node::branch(Node::join(a, left), right).rebalance()
+            }
+            (a, b) => Node::branch(a, b),
+        }
+    }
+
+    /// Split the tree into the first `idx` chars and the rest.
+    fn split(self, idx: usize) -> (Node, Node) {
+        match self {
+            Node::Leaf { text, .. } => {
+                let at = text.char_indices().nth(idx).map_or(text.len(), |(i, _)| i);
+                (Node::leaf(&text[..at]), Node::leaf(&text[at..]))
+            }
+            Node::Branch { left, right, .. } => {
+                let left_chars = left.chars();
+
+                if idx <= left_chars {
+                    let (a, b) = left.split(idx);
+                    (a, Node::join(b, *right))
+                } else {
+                    let (a, b) = right.split(idx - left_chars);
+                    (Node::join(*left, a), b)
+                }
+            }
+        }
+    }
+}
+
+/// A rope, i.e. text stored in a balanced tree of small chunks, for cheap
+/// edits in the middle of large texts.
+///
+/// Insertion and removal take `O(log n)`. All positions are char offsets.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::Rope;
+///
+/// let mut rope = Rope::from("Hello world!");
+/// rope.insert(5, ",");
+/// rope.remove(7..12);
+/// rope.insert(7, "мир");
+///
+/// assert_eq!(rope.to_string(), "Hello, мир!".to_string());
+/// assert_eq!(rope.slice(7..10), "мир".to_string());
+/// assert_eq!(rope.len(), 11);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rope {
+    root: Node,
+}
+
+impl Default for Rope {
+    fn default() -> Self {
+        Rope { root: Node::leaf("") }
+    }
+}
+
+impl From<&str> for Rope {
+    fn from(text: &str) -> Self {
+        Rope { root: Node::build(text) }
+    }
+}
+
+impl fmt::Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.chunks().try_for_each(|chunk| f.write_str(chunk))
+    }
+}
+
+impl Rope {
+    /// Create an empty rope.
+    pub fn new() -> Self {
+        Rope::default()
+    }
+
+    /// Return the number of chars.
+    pub fn len(&self) -> usize {
+        self.root.chars()
+    }
+
+    /// Return whether true or false if the rope is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the number of lines, i.e. one more than the number of `\n`.
+    pub fn len_lines(&self) -> usize {
+        self.root.newlines() + 1
+    }
+
+    /// Insert the text at the char offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is greater than the length of the rope.
+    pub fn insert(&mut self, idx: usize, text: &str) {
+        assert!(idx <= self.len(), "insertion index {} is out of bounds for length {}", idx, self.len());
+
+        let root = std::mem::take(&mut self.root);
+        let (left, right) = root.split(idx);
+
+        self.root = Node::join(Node::join(left, Node::build(text)), right);
+    }
+
+    /// Remove the chars in the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or ends after the rope.
+    pub fn remove(&mut self, range: Range<usize>) {
+        assert!(
+            range.start <= range.end && range.end <= self.len(),
+            "range {:?} is out of bounds for length {}",
+            range,
+            self.len()
+        );
+
+        let root = std::mem::take(&mut self.root);
+        let (rest, right) = root.split(range.end);
+        let (left, _) = rest.split(range.start);
+
+        self.root = Node::join(left, right);
+    }
+
+    /// Return the chars in the range, cut to the end of the rope. Takes
+    /// `O(log n + k)` for `k` chars.
+    pub fn slice(&self, range: Range<usize>) -> String {
+        let end = range.end.min(self.len());
+
+        RopeChars::new(&self.root, range.start.min(end)..end).collect()
+    }
+
+    /// Return the leaves' text in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::Rope;
+    ///
+    /// let rope = Rope::from("ab".repeat(1000).as_str());
+    /// assert_eq!(rope.chunks().count() > 1, true);
+    /// assert_eq!(rope.chunks().collect::<String>(), "ab".repeat(1000));
+    /// ```
+    pub fn chunks(&self) -> impl Iterator<Item = &str> {
+        let mut stack = vec![&self.root];
+
+        std::iter::from_fn(move || loop {
+            match stack.pop()? {
+                Node::Leaf { text, .. } if !text.is_empty() => return Some(text.as_str()),
+                Node::Leaf { .. } => {}
+                Node::Branch { left, right, .. } => {
+                    stack.push(right);
+                    stack.push(left);
+                }
+            }
+        })
+    }
+
+    /// Return an iterator over the chars.
+    pub fn chars(&self) -> RopeChars<'_> {
+        RopeChars::new(&self.root, 0..self.len())
+    }
+
+    /// Return the number of `\n` before the char offset, i.e. its line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::Rope;
+    ///
+    /// let rope = Rope::from("one\ntwo\nthree");
+    /// assert_eq!(rope.char_to_line(0), 0);
+    /// assert_eq!(rope.char_to_line(4), 1);
+    /// assert_eq!(rope.char_to_line(13), 2);
+    /// ```
+    pub fn char_to_line(&self, idx: usize) -> usize {
+        let mut node = &self.root;
+        let mut idx = idx.min(self.len());
+        let mut line = 0;
+
+        loop {
+            match node {
+                Node::Leaf { text, .. } => return line + text.chars().take(idx).filter(|&c| c == '\n').count(),
+                Node::Branch { left, right, .. } => {
+                    if idx < left.chars() {
+                        node = left;
+                    } else {
+                        idx -= left.chars();
+                        line += left.newlines();
+                        node = right;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Return the char offset where the line starts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line is not less than `len_lines`.
+    pub fn line_to_char(&self, line: usize) -> usize {
+        assert!(line < self.len_lines(), "line {} is out of bounds for {} lines", line, self.len_lines());
+
+        let mut node = &self.root;
+        let mut newlines = line;
+        let mut offset = 0;
+
+        if newlines == 0 {
+            return 0;
+        }
+
+        loop {
+            match node {
+                Node::Leaf { text, .. } => {
+                    let (i, _) = text.chars().enumerate().filter(|&(_, c)| c == '\n').nth(newlines - 1).unwrap();
+                    return offset + i + 1;
+                }
+                Node::Branch { left, right, .. } => {
+                    if newlines <= left.newlines() {
+                        node = left;
+                    } else {
+                        newlines -= left.newlines();
+                        offset += left.chars();
+                        node = right;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Return the line without its `\n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::Rope;
+    ///
+    /// let mut rope = Rope::from("one\ntwo\n");
+    /// rope.insert(4, "один\n");
+    ///
+    /// assert_eq!(rope.len_lines(), 4);
+    /// assert_eq!(rope.line(1), "один".to_string());
+    /// assert_eq!(rope.line(3), "".to_string());
+    /// ```
+    pub fn line(&self, line: usize) -> String {
+        let start = self.line_to_char(line);
+        let end = if line + 1 < self.len_lines() { self.line_to_char(line + 1) - 1 } else { self.len() };
+
+        self.slice(start..end)
+    }
+}
+
+impl Default for Node {
+    fn default() -> Self {
+        Node::leaf("")
+    }
+}
+
+/// An iterator over the chars of a [`Rope`].
+pub struct RopeChars<'a> {
+    root: &'a Node,
+    front: std::str::Chars<'a>,
+    back: std::str::Chars<'a>,
+    // The chars between `front` and `back` whose leaves are not read yet.
+    range: Range<usize>,
+}
+
+impl<'a> RopeChars<'a> {
+    fn new(root: &'a Node, range: Range<usize>) -> Self {
+        RopeChars { root, front: "".chars(), back: "".chars(), range }
+    }
+
+    /// Return the chars of the leaf holding the char offset that are inside
+    /// the range, and the range they cover.
+    fn leaf_chars(&self, idx: usize) -> (std::str::Chars<'a>, Range<usize>) {
+        let (text, start) = self.root.leaf_at(idx);
+        let from = self.range.start.max(start);
+        let to = self.range.end.min(start + text.chars().count());
+        let byte = |idx: usize| text.char_indices().nth(idx - start).map_or(text.len(), |(i, _)| i);
+
+        (text[byte(from)..byte(to)].chars(), from..to)
+    }
+}
+
+impl Iterator for RopeChars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        loop {
+            if let Some(c) = self.front.next() {
+                return Some(c);
+            }
+
+            if self.range.is_empty() {
+                return self.back.next();
+            }
+
+            let (chars, covered) = self.leaf_chars(self.range.start);
+            self.front = chars;
+            self.range.start = covered.end;
+        }
+    }
+}
+
+impl DoubleEndedIterator for RopeChars<'_> {
+    fn next_back(&mut self) -> Option<char> {
+        loop {
+            if let Some(c) = self.back.next_back() {
+                return Some(c);
+            }
+
+            if self.range.is_empty() {
+                return self.front.next_back();
+            }
+
+            let (chars, covered) = self.leaf_chars(self.range.end - 1);
+            self.back = chars;
+            self.range.end = covered.start;
+        }
+    }
+}
+
+impl CharSource for Rope {
+    type Chars<'a> = RopeChars<'a>;
+
+    fn source_chars(&self) -> Self::Chars<'_> {
+        self.chars()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_balance(node: &Node) {
+        if let Node::Branch { left, right, .. } = node {
+            assert!(left.height().abs_diff(right.height()) <= 1);
+            check_balance(left);
+            check_balance(right);
+        }
+    }
+
+    #[test]
+    fn test_edits_match_string() {
+        let mut rope = Rope::new();
+        let mut expected: Vec<char> = vec![];
+        let mut seed: u64 = 42;
+        let mut random = |n: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % n
+        };
+
+        for step in 0..2000 {
+            if random(3) > 0 || expected.is_empty() {
+                let text = ["ab", "вг\n", "日本", "x".repeat(600).as_str(), "\n"][random(5)].to_string();
+                let idx = random(expected.len() + 1);
+                rope.insert(idx, &text);
+                expected.splice(idx..idx, text.chars());
+            } else {
+                let start = random(expected.len());
+                let end = start + random(expected.len() - start + 1).min(700);
+                rope.remove(start..end);
+                expected.drain(start..end);
+            }
+
+            if step % 100 == 0 {
+                check_balance(&rope.root);
+            }
+        }
+
+        let expected: String = expected.into_iter().collect();
+        assert_eq!(rope.to_string(), expected);
+        assert_eq!(rope.len(), expected.chars().count());
+        assert_eq!(rope.len_lines(), expected.split('\n').count());
+        check_balance(&rope.root);
+
+        for (i, line) in expected.split('\n').enumerate() {
+            assert_eq!(rope.line(i), line.to_string());
+        }
+    }
+
+    #[test]
+    fn test_chars() {
+        let rope = Rope::from("абв");
+        assert_eq!(rope.chars().rev().collect::<String>(), "вба".to_string());
+        assert_eq!(Rope::new().chars().next(), None);
+        assert_eq!(Rope::new().len_lines(), 1);
+        assert_eq!(Rope::new().line(0), "".to_string());
+
+        let text: String = (0..3000).map(|i| ['a', 'б', '日', '\n'][i % 4]).collect();
+        let rope = Rope::from(text.as_str());
+        let mut chars = rope.chars();
+        assert_eq!(chars.next(), Some('a'));
+        assert_eq!(chars.next_back(), Some('\n'));
+        assert_eq!(chars.collect::<String>(), text[1..text.len() - 1].to_string());
+    }
+
+    #[test]
+    fn test_slice() {
+        let text: String = (0..3000).map(|i| ['a', 'б', '日', '\n'][i % 7 % 4]).collect();
+        let chars: Vec<char> = text.chars().collect();
+        let rope = Rope::from(text.as_str());
+
+        for (start, end) in [(0, 0), (0, 3000), (5, 6), (511, 1024), (1000, 2999), (2999, 3000)] {
+            assert_eq!(rope.slice(start..end), chars[start..end].iter().collect::<String>());
+        }
+
+        assert_eq!(rope.slice(2990..5000), chars[2990..].iter().collect::<String>());
+        assert_eq!(rope.slice(4000..5000), "".to_string());
+        assert_eq!(Rope::new().slice(0..1), "".to_string());
+    }
+}
@@ -0,0 +1,397 @@
+/// The back end used by [`find_all`] and [`find_all_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchAlgorithm {
+    /// Knuth-Morris-Pratt, `O(n + m)` in the worst case.
+    Kmp,
+    /// Z-algorithm, `O(n + m)` in the worst case.
+    Z,
+    /// Boyer-Moore-Horspool, sublinear on average but `O(n * m)` in the worst case.
+    Horspool,
+}
+
+/// Whether successive matches are allowed to share bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    /// Report every position where the pattern occurs.
+    Overlapping,
+    /// Report matches left to right, skipping those that overlap the previous one.
+    NonOverlapping,
+}
+
+/// Return the KMP failure function, where `result[i]` is the length of the
+/// longest proper prefix of `pattern[..=i]` that is also its suffix.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::failure_function;
+///
+/// let answer = failure_function(b"aabaaab");
+/// assert_eq!(answer, vec![0, 1, 0, 1, 2, 2, 3]);
+/// ```
+pub fn failure_function<T: PartialEq>(pattern: &[T]) -> Vec<usize> {
+    let mut failure = vec![0; pattern.len()];
+    let mut k = 0;
+
+    for i in 1..pattern.len() {
+        while k > 0 && pattern[i] != pattern[k] {
+            k = failure[k - 1];
+        }
+
+        if pattern[i] == pattern[k] {
+            k += 1;
+        }
+
+        failure[i] = k;
+    }
+
+    failure
+}
+
+/// Return the Z array, where `result[i]` is the length of the longest common
+/// prefix of `s` and `s[i..]`. By convention `result[0]` is `s.len()`.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::z_array;
+///
+/// let answer = z_array(b"aabxaab");
+/// assert_eq!(answer, vec![7, 1, 0, 0, 3, 1, 0]);
+/// ```
+pub fn z_array<T: PartialEq>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let mut z = vec![0; n];
+    let (mut left, mut right) = (0, 0);
+
+    if n > 0 {
+        z[0] = n;
+    }
+
+    for i in 1..n {
+        if i < right {
+            z[i] = z[i - left].min(right - i);
+        }
+
+        while i + z[i] < n && s[z[i]] == s[i + z[i]] {
+            z[i] += 1;
+        }
+
+        if i + z[i] > right {
+            left = i;
+            right = i + z[i];
+        }
+    }
+
+    z
+}
+
+fn empty_matches(len: usize) -> Vec<usize> {
+    (0..=len).collect()
+}
+
+/// A precompiled Knuth-Morris-Pratt searcher.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{KmpSearcher, MatchMode};
+///
+/// let searcher = KmpSearcher::new(b"aa");
+/// assert_eq!(searcher.find_all_bytes(b"aaaa", MatchMode::Overlapping), vec![0, 1, 2]);
+/// assert_eq!(searcher.find_all_bytes(b"aaaa", MatchMode::NonOverlapping), vec![0, 2]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KmpSearcher {
+    pattern: Vec<u8>,
+    failure: Vec<usize>,
+}
+
+impl KmpSearcher {
+    pub fn new(pattern: &[u8]) -> Self {
+        KmpSearcher { pattern: pattern.to_vec(), failure: failure_function(pattern) }
+    }
+
+    /// Return the failure function of the pattern.
+    pub fn failure(&self) -> &[usize] {
+        &self.failure
+    }
+
+    /// Return the byte offsets of the matches in the text.
+    pub fn find_all(&self, text: &str, mode: MatchMode) -> Vec<usize> {
+        to_char_boundaries(text, self.pattern.is_empty(), self.find_all_bytes(text.as_bytes(), mode))
+    }
+
+    /// Return the offsets of the matches in the bytes.
+    pub fn find_all_bytes(&self, text: &[u8], mode: MatchMode) -> Vec<usize> {
+        let m = self.pattern.len();
+
+        if m == 0 {
+            return empty_matches(text.len());
+        }
+
+        let mut result = vec![];
+        let mut k = 0;
+
+        for (i, &b) in text.iter().enumerate() {
+            while k > 0 && b != self.pattern[k] {
+                k = self.failure[k - 1];
+            }
+
+            if b == self.pattern[k] {
+                k += 1;
+            }
+
+            if k == m {
+                result.push(i + 1 - m);
+                k = match mode {
+                    MatchMode::Overlapping => self.failure[k - 1],
+                    MatchMode::NonOverlapping => 0,
+                };
+            }
+        }
+
+        result
+    }
+}
+
+/// A precompiled Z-algorithm searcher.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{MatchMode, ZSearcher};
+///
+/// let searcher = ZSearcher::new(b"aba");
+/// assert_eq!(searcher.find_all_bytes(b"ababa", MatchMode::Overlapping), vec![0, 2]);
+/// assert_eq!(searcher.find_all_bytes(b"ababa", MatchMode::NonOverlapping), vec![0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ZSearcher {
+    pattern: Vec<u8>,
+    z: Vec<usize>,
+}
+
+impl ZSearcher {
+    pub fn new(pattern: &[u8]) -> Self {
+        ZSearcher { pattern: pattern.to_vec(), z: z_array(pattern) }
+    }
+
+    /// Return the Z array of the pattern.
+    pub fn z(&self) -> &[usize] {
+        &self.z
+    }
+
+    /// Return the byte offsets of the matches in the text.
+    pub fn find_all(&self, text: &str, mode: MatchMode) -> Vec<usize> {
+        to_char_boundaries(text, self.pattern.is_empty(), self.find_all_bytes(text.as_bytes(), mode))
+    }
+
+    /// Return the offsets of the matches in the bytes.
+    pub fn find_all_bytes(&self, text: &[u8], mode: MatchMode) -> Vec<usize> {
+        let (m, n) = (self.pattern.len(), text.len());
+
+        if m == 0 {
+            return empty_matches(n);
+        }
+
+        let mut result = vec![];
+        let mut next_allowed = 0;
+        // Invariant: text[left..right] == pattern[..right - left].
+        let (mut left, mut right) = (0, 0);
+
+        for i in 0..n {
+            let mut k = if i < right { self.z[i - left].min(right - i) } else { 0 };
+
+            if i + k >= right {
+                while k < m && i + k < n && text[i + k] == self.pattern[k] {
+                    k += 1;
+                }
+
+                if i + k > right {
+                    left = i;
+                    right = i + k;
+                }
+            }
+
+            if k == m && i >= next_allowed {
+                result.push(i);
+
+                if mode == MatchMode::NonOverlapping {
+                    next_allowed = i + m;
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// A precompiled Boyer-Moore-Horspool searcher.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{HorspoolSearcher, MatchMode};
+///
+/// let searcher = HorspoolSearcher::new(b"needle");
+/// assert_eq!(searcher.find_all_bytes(b"haystack with a needle", MatchMode::Overlapping), vec![16]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HorspoolSearcher {
+    pattern: Vec<u8>,
+    shift: Vec<usize>,
+}
+
+impl HorspoolSearcher {
+    pub fn new(pattern: &[u8]) -> Self {
+        let m = pattern.len();
+        let mut shift = vec![m.max(1); 256];
+
+        for (i, &b) in pattern.iter().enumerate().take(m.saturating_sub(1)) {
+            shift[b as usize] = m - 1 - i;
+        }
+
+        HorspoolSearcher { pattern: pattern.to_vec(), shift }
+    }
+
+    /// Return the byte offsets of the matches in the text.
+    pub fn find_all(&self, text: &str, mode: MatchMode) -> Vec<usize> {
+        to_char_boundaries(text, self.pattern.is_empty(), self.find_all_bytes(text.as_bytes(), mode))
+    }
+
+    /// Return the offsets of the matches in the bytes.
+    pub fn find_all_bytes(&self, text: &[u8], mode: MatchMode) -> Vec<usize> {
+        let (m, n) = (self.pattern.len(), text.len());
+
+        if m == 0 {
+            return empty_matches(n);
+        }
+
+        let mut result = vec![];
+        let mut i = 0;
+
+        while i + m <= n {
+            let last = text[i + m - 1];
+
+            if last == self.pattern[m - 1] && text[i..i + m - 1] == self.pattern[..m - 1] {
+                result.push(i);
+
+                if mode == MatchMode::NonOverlapping {
+                    i += m;
+                    continue;
+                }
+            }
+
+            i += self.shift[last as usize];
+        }
+
+        result
+    }
+}
+
+/// Matches of a non-empty UTF-8 pattern always start on a char boundary,
+/// only the positions of an empty pattern need to be filtered.
+fn to_char_boundaries(text: &str, is_empty_pattern: bool, positions: Vec<usize>) -> Vec<usize> {
+    if !is_empty_pattern {
+        return positions;
+    }
+
+    positions.into_iter().filter(|&i| text.is_char_boundary(i)).collect()
+}
+
+/// Return the byte offsets of every occurrence of the pattern in the text.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{find_all, MatchMode, SearchAlgorithm};
+///
+/// let answer = find_all("кек", "кекекек", SearchAlgorithm::Kmp, MatchMode::Overlapping);
+/// assert_eq!(answer, vec![0, 4, 8]);
+///
+/// let answer = find_all("кек", "кекекек", SearchAlgorithm::Z, MatchMode::NonOverlapping);
+/// assert_eq!(answer, vec![0, 8]);
+/// ```
+pub fn find_all(pattern: &str, text: &str, algorithm: SearchAlgorithm, mode: MatchMode) -> Vec<usize> {
+    to_char_boundaries(
+        text,
+        pattern.is_empty(),
+        find_all_bytes(pattern.as_bytes(), text.as_bytes(), algorithm, mode),
+    )
+}
+
+/// Return the offsets of every occurrence of the pattern in the bytes.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{find_all_bytes, MatchMode, SearchAlgorithm};
+///
+/// let answer = find_all_bytes(&[0, 1], &[0, 1, 0, 1], SearchAlgorithm::Horspool, MatchMode::Overlapping);
+/// assert_eq!(answer, vec![0, 2]);
+/// ```
+pub fn find_all_bytes(pattern: &[u8], text: &[u8], algorithm: SearchAlgorithm, mode: MatchMode) -> Vec<usize> {
+    match algorithm {
+        SearchAlgorithm::Kmp => KmpSearcher::new(pattern).find_all_bytes(text, mode),
+        SearchAlgorithm::Z => ZSearcher::new(pattern).find_all_bytes(text, mode),
+        SearchAlgorithm::Horspool => HorspoolSearcher::new(pattern).find_all_bytes(text, mode),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALGORITHMS: [SearchAlgorithm; 3] = [SearchAlgorithm::Kmp, SearchAlgorithm::Z, SearchAlgorithm::Horspool];
+
+    #[test]
+    fn test_find_all() {
+        for algorithm in ALGORITHMS {
+            let answer = find_all("aa", "aaaaa", algorithm, MatchMode::Overlapping);
+            assert_eq!(answer, vec![0, 1, 2, 3]);
+
+            let answer = find_all("aa", "aaaaa", algorithm, MatchMode::NonOverlapping);
+            assert_eq!(answer, vec![0, 2]);
+
+            let answer = find_all("abcab", "abcabcabcab", algorithm, MatchMode::Overlapping);
+            assert_eq!(answer, vec![0, 3, 6]);
+
+            let answer = find_all("xyz", "abc", algorithm, MatchMode::Overlapping);
+            assert_eq!(answer, vec![]);
+
+            let answer = find_all("", "ёж", algorithm, MatchMode::Overlapping);
+            assert_eq!(answer, vec![0, 2, 4]);
+        }
+    }
+
+    #[test]
+    fn test_find_all_bytes() {
+        let text: Vec<u8> = (0..200).map(|i| (i * 7 % 5) as u8).collect();
+        let pattern = [2, 4, 1];
+        let expected: Vec<usize> = (0..=text.len() - 3).filter(|&i| text[i..i + 3] == pattern).collect();
+
+        for algorithm in ALGORITHMS {
+            let answer = find_all_bytes(&pattern, &text, algorithm, MatchMode::Overlapping);
+            assert_eq!(answer, expected);
+        }
+    }
+
+    #[test]
+    fn test_failure_function() {
+        let answer = failure_function(&['a', 'b', 'a', 'b', 'a', 'c']);
+        assert_eq!(answer, vec![0, 0, 1, 2, 3, 0]);
+
+        let answer = failure_function::<u8>(&[]);
+        assert_eq!(answer, vec![]);
+    }
+
+    #[test]
+    fn test_z_array() {
+        let answer = z_array(b"aaaaa");
+        assert_eq!(answer, vec![5, 4, 3, 2, 1]);
+
+        let answer = z_array(b"abacaba");
+        assert_eq!(answer, vec![7, 0, 1, 0, 3, 0, 1]);
+    }
+}
@@ -0,0 +1,223 @@
+/// Return the Latin spelling of a lowercase Cyrillic, Greek or accented
+/// Latin letter.
+#[rustfmt::skip]
+fn latin(c: char) -> Option<&'static str> {
+    let latin = match c {
+        // Cyrillic, including Ukrainian and Belarusian letters.
+        'а' => "a", 'б' => "b", 'в' => "v", 'г' => "g", 'д' => "d", 'е' => "e",
+        'ё' => "yo", 'ж' => "zh", 'з' => "z", 'и' => "i", 'й' => "y", 'к' => "k",
+        'л' => "l", 'м' => "m", 'н' => "n", 'о' => "o", 'п' => "p", 'р' => "r",
+        'с' => "s", 'т' => "t", 'у' => "u", 'ф' => "f", 'х' => "kh", 'ц' => "ts",
+        'ч' => "ch", 'ш' => "sh", 'щ' => "shch", 'ъ' => "", 'ы' => "y", 'ь' => "",
+        'э' => "e", 'ю' => "yu", 'я' => "ya", 'є' => "ye", 'і' => "i", 'ї' => "yi",
+        'ґ' => "g", 'ў' => "u",
+        // Greek, with and without tonos and dialytika.
+        'α' | 'ά' => "a", 'β' => "v", 'γ' => "g", 'δ' => "d", 'ε' | 'έ' => "e",
+        'ζ' => "z", 'η' | 'ή' => "i", 'θ' => "th", 'ι' | 'ί' | 'ϊ' | 'ΐ' => "i",
+        'κ' => "k", 'λ' => "l", 'μ' => "m", 'ν' => "n", 'ξ' => "x", 'ο' | 'ό' => "o",
+        'π' => "p", 'ρ' => "r", 'σ' | 'ς' => "s", 'τ' => "t", 'υ' | 'ύ' | 'ϋ' | 'ΰ' => "y",
+        'φ' => "f", 'χ' => "ch", 'ψ' => "ps", 'ω' | 'ώ' => "o",
+        // Latin-1 Supplement and Latin Extended-A.
+        'à'..='å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ð' | 'ď' | 'đ' => "d",
+        'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĳ' => "ij",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ŋ' => "ng",
+        'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ſ' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'þ' => "th",
+        'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+
+    Some(latin)
+}
+
+/// Transliterate Cyrillic, Greek and accented Latin letters into ASCII and
+/// drop combining diacritical marks. Capital letters stay capital, and
+/// their whole transliteration is capital inside an all-caps word; other
+/// chars are kept as they are.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::transliterate;
+///
+/// assert_eq!(transliterate("Щука и ёж"), "Shchuka i yozh".to_string());
+/// assert_eq!(transliterate("Αθήνα"), "Athina".to_string());
+/// assert_eq!(transliterate("Crème brûlée, Straße"), "Creme brulee, Strasse".to_string());
+/// assert_eq!(transliterate("e\u{301}te\u{301}"), "ete".to_string());
+/// assert_eq!(transliterate("ЩИ и ЁЖ"), "SHCHI i YOZH".to_string());
+/// ```
+pub fn transliterate(s: &str) -> String {
+    let chars: Vec<char> = s.chars().filter(|c| !('\u{300}'..='\u{36F}').contains(c)).collect();
+    let mut result = String::with_capacity(s.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        let lower = c.to_lowercase().next().unwrap_or(c);
+
+        match latin(lower) {
+            Some(latin) if c != lower => {
+                // Inside an all-caps word the next letter, or the previous one at its end, is capital too.
+                let is_all_caps = match chars.get(i + 1) {
+                    Some(next) if next.is_alphabetic() => next.is_uppercase(),
+                    _ => i > 0 && chars[i - 1].is_uppercase(),
+                };
+
+                if is_all_caps {
+                    result.push_str(&latin.to_ascii_uppercase());
+                } else {
+                    let mut chars = latin.chars();
+                    result.extend(chars.next().map(|first| first.to_ascii_uppercase()));
+                    result.push_str(chars.as_str());
+                }
+            }
+            Some(latin) => result.push_str(latin),
+            // Capitals such as `İ` whose lowercase starts with an ASCII letter.
+            None if c != lower && lower.is_ascii() => result.push(lower.to_ascii_uppercase()),
+            None => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Options of [`slugify_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlugOptions {
+    /// The string put between words.
+    pub separator: String,
+    /// The maximum number of chars of the slug. Words that don't fit are
+    /// dropped whole; only a single first word that doesn't fit is cut.
+    pub max_length: Option<usize>,
+    /// Whether true or false if the slug is lowercased.
+    pub lowercase: bool,
+}
+
+impl Default for SlugOptions {
+    fn default() -> Self {
+        SlugOptions { separator: "-".to_string(), max_length: None, lowercase: true }
+    }
+}
+
+/// Return a lowercase, `-`-separated slug of the text for URLs and file names.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::slugify;
+///
+/// assert_eq!(slugify("Привет, мир!"), "privet-mir".to_string());
+/// assert_eq!(slugify("  Ελληνικά & Français  "), "ellinika-francais".to_string());
+/// ```
+pub fn slugify(s: &str) -> String {
+    slugify_with(s, &SlugOptions::default())
+}
+
+/// Return a slug of the text: transliterated words of letters and digits
+/// joined by the separator.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{slugify_with, SlugOptions};
+///
+/// let options = SlugOptions { separator: "_".to_string(), max_length: Some(16), lowercase: false };
+/// let answer = slugify_with("Как настроить Rust на Linux", &options);
+/// assert_eq!(answer, "Kak_nastroit".to_string());
+/// ```
+pub fn slugify_with(s: &str, options: &SlugOptions) -> String {
+    let text = transliterate(s);
+    let text = if options.lowercase { text.to_lowercase() } else { text };
+    let separator_len = options.separator.chars().count();
+    let mut slug = String::new();
+    let mut slug_len = 0;
+
+    for word in text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+        let word_len = word.chars().count();
+        let gap = if slug.is_empty() { 0 } else { separator_len };
+
+        if let Some(max_length) = options.max_length {
+            if slug.is_empty() && word_len > max_length {
+                return word.chars().take(max_length).collect();
+            }
+
+            if slug_len + gap + word_len > max_length {
+                break;
+            }
+        }
+
+        if gap > 0 {
+            slug.push_str(&options.separator);
+        }
+
+        slug.push_str(word);
+        slug_len += gap + word_len;
+    }
+
+    slug
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transliterate() {
+        assert_eq!(transliterate(""), "".to_string());
+        assert_eq!(transliterate("Київ, Ґанок, Їжак"), "Kiyiv, Ganok, Yizhak".to_string());
+        assert_eq!(transliterate("ΣΟΦΟΣ"), "SOFOS".to_string());
+        assert_eq!(transliterate("Łódź İstanbul"), "Lodz Istanbul".to_string());
+        assert_eq!(transliterate("日本"), "日本".to_string());
+    }
+
+    #[test]
+    fn test_transliterate_all_caps() {
+        assert_eq!(transliterate("ЩИ"), "SHCHI".to_string());
+        assert_eq!(transliterate("ЖЖ"), "ZHZH".to_string());
+        assert_eq!(transliterate("Ж, ЩУКА!"), "Zh, SHCHUKA!".to_string());
+        assert_eq!(transliterate("ЁЖик"), "YOZhik".to_string());
+        assert_eq!(transliterate("ΨΥΧΗ"), "PSYCHI".to_string());
+
+        let options = SlugOptions { lowercase: false, ..SlugOptions::default() };
+        assert_eq!(slugify_with("ЖЖ Щи", &options), "ZHZH-Shchi".to_string());
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify(""), "".to_string());
+        assert_eq!(slugify("--- ! ---"), "".to_string());
+        assert_eq!(slugify("Съешь же ещё этих мягких булок"), "sesh-zhe-eshchyo-etikh-myagkikh-bulok".to_string());
+        assert_eq!(slugify("Rust 1.75 — what's new?"), "rust-1-75-what-s-new".to_string());
+        assert_eq!(slugify("日本語 テキスト"), "日本語-テキスト".to_string());
+    }
+
+    #[test]
+    fn test_slugify_max_length() {
+        let options = |max_length| SlugOptions { max_length: Some(max_length), ..SlugOptions::default() };
+
+        assert_eq!(slugify_with("hello big world", &options(9)), "hello-big".to_string());
+        assert_eq!(slugify_with("hello big world", &options(8)), "hello".to_string());
+        assert_eq!(slugify_with("hello big world", &options(3)), "hel".to_string());
+        assert_eq!(slugify_with("hello", &options(0)), "".to_string());
+
+        let options = SlugOptions { separator: "--".to_string(), max_length: Some(10), lowercase: true };
+        assert_eq!(slugify_with("ab cd ef", &options), "ab--cd--ef".to_string());
+    }
+}
@@ -0,0 +1,305 @@
+use std::cmp::Ordering;
+
+/// Return the suffix array of the sequence, i.e. the starting positions of
+/// its suffixes in lexicographic order. Built by prefix doubling.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::suffix_array;
+///
+/// let answer = suffix_array(b"banana");
+/// assert_eq!(answer, vec![5, 3, 1, 0, 4, 2]);
+/// ```
+pub fn suffix_array<T: Ord>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let mut sa: Vec<usize> = (0..n).collect();
+    sa.sort_by(|&a, &b| s[a].cmp(&s[b]));
+
+    let mut rank = vec![0; n];
+    for i in 1..n {
+        rank[sa[i]] = rank[sa[i - 1]] + usize::from(s[sa[i]] != s[sa[i - 1]]);
+    }
+
+    let mut k = 1;
+    let mut next = vec![0; n];
+
+    while k < n && rank[sa[n - 1]] < n - 1 {
+        // Rank 0 is kept for "past the end", so shift the real ranks by one.
+        let key = |i: usize| (rank[i] + 1, if i + k < n { rank[i + k] + 1 } else { 0 });
+        sa.sort_unstable_by_key(|&i| key(i));
+
+        next[sa[0]] = 0;
+        for i in 1..n {
+            next[sa[i]] = next[sa[i - 1]] + usize::from(key(sa[i]) != key(sa[i - 1]));
+        }
+
+        std::mem::swap(&mut rank, &mut next);
+        k *= 2;
+    }
+
+    sa
+}
+
+/// Return the LCP array built with Kasai's algorithm, where `result[i]` is the
+/// length of the longest common prefix of the suffixes `sa[i - 1]` and `sa[i]`.
+/// `result[0]` is always `0`.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{lcp_array, suffix_array};
+///
+/// let sa = suffix_array(b"banana");
+/// let answer = lcp_array(b"banana", &sa);
+/// assert_eq!(answer, vec![0, 1, 3, 0, 0, 2]);
+/// ```
+pub fn lcp_array<T: Eq>(s: &[T], sa: &[usize]) -> Vec<usize> {
+    let n = s.len();
+    let mut rank = vec![0; n];
+    let mut lcp = vec![0; n];
+    let mut h = 0;
+
+    for (i, &p) in sa.iter().enumerate() {
+        rank[p] = i;
+    }
+
+    for i in 0..n {
+        if rank[i] == 0 {
+            h = 0;
+            continue;
+        }
+
+        let j = sa[rank[i] - 1];
+
+        while i + h < n && j + h < n && s[i + h] == s[j + h] {
+            h += 1;
+        }
+
+        lcp[rank[i]] = h;
+        h = h.saturating_sub(1);
+    }
+
+    lcp
+}
+
+/// A suffix array over the chars of a string together with its LCP array.
+/// Positions are char indices, not byte offsets.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::SuffixArray;
+///
+/// let sa = SuffixArray::new("abracadabra");
+/// assert_eq!(sa.count_occurrences("abra"), 2);
+/// assert_eq!(sa.longest_repeated_substring(), "abra".to_string());
+/// assert_eq!(sa.distinct_substrings(), 54);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SuffixArray {
+    chars: Vec<char>,
+    sa: Vec<usize>,
+    lcp: Vec<usize>,
+}
+
+impl SuffixArray {
+    pub fn new(text: &str) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        let sa = suffix_array(&chars);
+        let lcp = lcp_array(&chars, &sa);
+
+        SuffixArray { chars, sa, lcp }
+    }
+
+    /// Return the sorted suffix positions.
+    pub fn suffixes(&self) -> &[usize] {
+        &self.sa
+    }
+
+    /// Return the LCP array.
+    pub fn lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    fn compare(&self, suffix: usize, pattern: &[char]) -> Ordering {
+        let end = (suffix + pattern.len()).min(self.chars.len());
+
+        self.chars[suffix..end].cmp(pattern)
+    }
+
+    /// Return the range of `sa` whose suffixes start with the pattern.
+    fn range(&self, pattern: &str) -> std::ops::Range<usize> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let from = self.sa.partition_point(|&s| self.compare(s, &pattern) == Ordering::Less);
+        let to = self.sa.partition_point(|&s| self.compare(s, &pattern) != Ordering::Greater);
+
+        from..to
+    }
+
+    /// Return the char positions where the pattern occurs, in increasing order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::SuffixArray;
+    ///
+    /// let sa = SuffixArray::new("мама мыла раму");
+    /// assert_eq!(sa.occurrences("ам"), vec![1, 11]);
+    /// ```
+    pub fn occurrences(&self, pattern: &str) -> Vec<usize> {
+        let mut result = self.sa[self.range(pattern)].to_vec();
+
+        result.sort_unstable();
+        result
+    }
+
+    /// Return how many times the pattern occurs, overlapping occurrences included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::SuffixArray;
+    ///
+    /// let sa = SuffixArray::new("aaaa");
+    /// assert_eq!(sa.count_occurrences("aa"), 3);
+    /// assert_eq!(sa.count_occurrences("b"), 0);
+    /// ```
+    pub fn count_occurrences(&self, pattern: &str) -> usize {
+        self.range(pattern).len()
+    }
+
+    /// Return the longest substring that occurs at least twice. Among several
+    /// of the same length the lexicographically smallest one is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::SuffixArray;
+    ///
+    /// let sa = SuffixArray::new("banana");
+    /// assert_eq!(sa.longest_repeated_substring(), "ana".to_string());
+    ///
+    /// let sa = SuffixArray::new("abc");
+    /// assert_eq!(sa.longest_repeated_substring(), "".to_string());
+    /// ```
+    pub fn longest_repeated_substring(&self) -> String {
+        let best = (0..self.lcp.len()).fold(0, |best, i| if self.lcp[i] > self.lcp[best] { i } else { best });
+
+        match self.lcp.get(best) {
+            Some(&len) if len > 0 => self.chars[self.sa[best]..self.sa[best] + len].iter().collect(),
+            _ => String::new(),
+        }
+    }
+
+    /// Return the number of distinct non-empty substrings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::SuffixArray;
+    ///
+    /// let sa = SuffixArray::new("aaa");
+    /// assert_eq!(sa.distinct_substrings(), 3);
+    /// ```
+    pub fn distinct_substrings(&self) -> usize {
+        let n = self.chars.len();
+
+        n * (n + 1) / 2 - self.lcp.iter().sum::<usize>()
+    }
+}
+
+/// Return the longest common substring of two strings. Among several of the
+/// same length the lexicographically smallest one is returned.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::longest_common_substring;
+///
+/// let answer = longest_common_substring("xabcdey", "zzbcdq");
+/// assert_eq!(answer, "bcd".to_string());
+///
+/// let answer = longest_common_substring("abc", "xyz");
+/// assert_eq!(answer, "".to_string());
+/// ```
+pub fn longest_common_substring(a: &str, b: &str) -> String {
+    // Chars are shifted by one so that 0 is a separator that occurs nowhere else.
+    let split = a.chars().count();
+    let joined: Vec<u32> = a
+        .chars()
+        .map(|c| c as u32 + 1)
+        .chain(std::iter::once(0))
+        .chain(b.chars().map(|c| c as u32 + 1))
+        .collect();
+
+    let sa = suffix_array(&joined);
+    let lcp = lcp_array(&joined, &sa);
+    let (mut best_len, mut best_pos) = (0, 0);
+
+    for i in 1..sa.len() {
+        if (sa[i] < split) != (sa[i - 1] < split) && lcp[i] > best_len {
+            best_len = lcp[i];
+            best_pos = sa[i];
+        }
+    }
+
+    joined[best_pos..best_pos + best_len]
+        .iter()
+        .filter_map(|&c| char::from_u32(c - 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_suffix_array(s: &[u8]) -> Vec<usize> {
+        let mut sa: Vec<usize> = (0..s.len()).collect();
+        sa.sort_by_key(|&i| &s[i..]);
+        sa
+    }
+
+    #[test]
+    fn test_suffix_array() {
+        let text: Vec<u8> = (0..300).map(|i| b"abca"[(i * i + i / 7) % 4]).collect();
+        let answer = suffix_array(&text);
+        assert_eq!(answer, naive_suffix_array(&text));
+
+        let answer = suffix_array::<u8>(&[]);
+        assert_eq!(answer, vec![]);
+
+        let answer = suffix_array(b"aaaa");
+        assert_eq!(answer, vec![3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_lcp_array() {
+        let sa = suffix_array(b"mississippi");
+        let answer = lcp_array(b"mississippi", &sa);
+        assert_eq!(answer, vec![0, 1, 1, 4, 0, 0, 1, 0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn test_suffix_array_queries() {
+        let sa = SuffixArray::new("mississippi");
+        assert_eq!(sa.count_occurrences("ss"), 2);
+        assert_eq!(sa.count_occurrences("i"), 4);
+        assert_eq!(sa.count_occurrences(""), 11);
+        assert_eq!(sa.occurrences("issi"), vec![1, 4]);
+        assert_eq!(sa.longest_repeated_substring(), "issi".to_string());
+
+        let sa = SuffixArray::new("");
+        assert_eq!(sa.distinct_substrings(), 0);
+        assert_eq!(sa.longest_repeated_substring(), "".to_string());
+    }
+
+    #[test]
+    fn test_longest_common_substring() {
+        let answer = longest_common_substring("привет мир", "мирный");
+        assert_eq!(answer, "мир".to_string());
+
+        let answer = longest_common_substring("", "abc");
+        assert_eq!(answer, "".to_string());
+    }
+}
@@ -0,0 +1,325 @@
+use std::cell::OnceCell;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone)]
+struct State {
+    len: usize,
+    link: Option<usize>,
+    next: BTreeMap<char, usize>,
+    /// Char index of the last char of the first occurrence.
+    first_end: usize,
+    is_clone: bool,
+}
+
+/// A suffix automaton over the chars of a string, built online in linear time.
+/// Positions are char indices, not byte offsets.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::SuffixAutomaton;
+///
+/// let sam = SuffixAutomaton::new("abcbc");
+/// assert_eq!(sam.contains("cbc"), true);
+/// assert_eq!(sam.contains("ca"), false);
+/// assert_eq!(sam.count_occurrences("bc"), 2);
+/// assert_eq!(sam.first_occurrence("bc"), Some(1));
+/// assert_eq!(sam.distinct_substrings(), 12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SuffixAutomaton {
+    states: Vec<State>,
+    last: usize,
+    len: usize,
+    occurrences: OnceCell<Vec<usize>>,
+    paths: OnceCell<Vec<usize>>,
+}
+
+impl Default for SuffixAutomaton {
+    fn default() -> Self {
+        SuffixAutomaton {
+            states: vec![State { len: 0, link: None, next: BTreeMap::new(), first_end: 0, is_clone: false }],
+            last: 0,
+            len: 0,
+            occurrences: OnceCell::new(),
+            paths: OnceCell::new(),
+        }
+    }
+}
+
+impl SuffixAutomaton {
+    pub fn new(text: &str) -> Self {
+        let mut sam = SuffixAutomaton::default();
+
+        sam.extend(text);
+        sam
+    }
+
+    /// Append every char of the text.
+    pub fn extend(&mut self, text: &str) {
+        text.chars().for_each(|c| self.push(c));
+    }
+
+    /// Append one char, in amortized constant time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::SuffixAutomaton;
+    ///
+    /// let mut sam = SuffixAutomaton::new("ab");
+    /// assert_eq!(sam.contains("bc"), false);
+    ///
+    /// sam.push('c');
+    /// assert_eq!(sam.contains("bc"), true);
+    /// ```
+    pub fn push(&mut self, c: char) {
+        self.occurrences.take();
+        self.paths.take();
+
+        let current = self.states.len();
+        self.states.push(State {
+            len: self.states[self.last].len + 1,
+            link: None,
+            next: BTreeMap::new(),
+            first_end: self.len,
+            is_clone: false,
+        });
+        self.len += 1;
+
+        let mut p = Some(self.last);
+        while let Some(state) = p.filter(|&s| !self.states[s].next.contains_key(&c)) {
+            self.states[state].next.insert(c, current);
+            p = self.states[state].link;
+        }
+
+        let link = match p {
+            None => 0,
+            Some(p) => {
+                let q = self.states[p].next[&c];
+
+                if self.states[p].len + 1 == self.states[q].len {
+                    q
+                } else {
+                    let clone = self.states.len();
+                    self.states.push(State {
+                        len: self.states[p].len + 1,
+                        is_clone: true,
+                        ..self.states[q].clone()
+                    });
+
+                    let mut p = Some(p);
+                    while let Some(state) = p.filter(|&s| self.states[s].next.get(&c) == Some(&q)) {
+                        self.states[state].next.insert(c, clone);
+                        p = self.states[state].link;
+                    }
+
+                    self.states[q].link = Some(clone);
+                    clone
+                }
+            }
+        };
+
+        self.states[current].link = Some(link);
+        self.last = current;
+    }
+
+    /// Return the number of chars in the indexed text.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return whether true or false if the indexed text is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn walk(&self, pattern: &str) -> Option<usize> {
+        pattern.chars().try_fold(0, |state, c| self.states[state].next.get(&c).copied())
+    }
+
+    /// States ordered by decreasing `len`, so every state comes before its
+    /// suffix link and after all the states it has transitions to.
+    fn by_len_desc(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.states.len()).collect();
+
+        order.sort_unstable_by_key(|&s| std::cmp::Reverse(self.states[s].len));
+        order
+    }
+
+    fn occurrences(&self) -> &[usize] {
+        self.occurrences.get_or_init(|| {
+            let mut count: Vec<usize> = self.states.iter().map(|s| usize::from(!s.is_clone)).collect();
+            count[0] = 0;
+
+            for s in self.by_len_desc() {
+                if let Some(link) = self.states[s].link {
+                    count[link] += count[s];
+                }
+            }
+
+            count
+        })
+    }
+
+    fn paths(&self) -> &[usize] {
+        self.paths.get_or_init(|| {
+            let mut paths = vec![1; self.states.len()];
+
+            for s in self.by_len_desc() {
+                paths[s] += self.states[s].next.values().map(|&t| paths[t]).sum::<usize>();
+            }
+
+            paths
+        })
+    }
+
+    /// Return whether true or false if the pattern is a substring of the text.
+    pub fn contains(&self, pattern: &str) -> bool {
+        self.walk(pattern).is_some()
+    }
+
+    /// Return how many times the pattern occurs, overlapping occurrences
+    /// included. The empty pattern is counted once per char.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::SuffixAutomaton;
+    ///
+    /// let sam = SuffixAutomaton::new("aaaa");
+    /// assert_eq!(sam.count_occurrences("aa"), 3);
+    /// assert_eq!(sam.count_occurrences("b"), 0);
+    /// ```
+    pub fn count_occurrences(&self, pattern: &str) -> usize {
+        match self.walk(pattern) {
+            Some(0) => self.len,
+            Some(state) => self.occurrences()[state],
+            None => 0,
+        }
+    }
+
+    /// Return the char index where the pattern first occurs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::SuffixAutomaton;
+    ///
+    /// let sam = SuffixAutomaton::new("привет мир");
+    /// assert_eq!(sam.first_occurrence("ми"), Some(7));
+    /// assert_eq!(sam.first_occurrence("кек"), None);
+    /// ```
+    pub fn first_occurrence(&self, pattern: &str) -> Option<usize> {
+        match self.walk(pattern)? {
+            0 => Some(0),
+            state => Some(self.states[state].first_end + 1 - pattern.chars().count()),
+        }
+    }
+
+    /// Return the `k`-th (0-based) distinct non-empty substring in lexicographic order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::SuffixAutomaton;
+    ///
+    /// let sam = SuffixAutomaton::new("aba");
+    /// assert_eq!(sam.kth_substring(0), Some("a".to_string()));
+    /// assert_eq!(sam.kth_substring(1), Some("ab".to_string()));
+    /// assert_eq!(sam.kth_substring(4), Some("ba".to_string()));
+    /// assert_eq!(sam.kth_substring(5), None);
+    /// ```
+    pub fn kth_substring(&self, k: usize) -> Option<String> {
+        let paths = self.paths();
+        let mut k = k;
+        let mut state = 0;
+        let mut result = String::new();
+
+        if k + 1 >= paths[0] {
+            return None;
+        }
+
+        loop {
+            for (&c, &next) in &self.states[state].next {
+                if k < paths[next] {
+                    result.push(c);
+                    state = next;
+                    break;
+                }
+                k -= paths[next];
+            }
+
+            if k == 0 {
+                return Some(result);
+            }
+            k -= 1;
+        }
+    }
+
+    /// Return the number of distinct non-empty substrings.
+    pub fn distinct_substrings(&self) -> usize {
+        self.states
+            .iter()
+            .filter_map(|s| s.link.map(|link| s.len - self.states[link].len))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn all_substrings(text: &str) -> BTreeSet<String> {
+        let chars: Vec<char> = text.chars().collect();
+
+        (0..chars.len())
+            .flat_map(|i| (i + 1..=chars.len()).map(move |j| (i, j)))
+            .map(|(i, j)| chars[i..j].iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_contains_and_count() {
+        let sam = SuffixAutomaton::new("abracadabra");
+        assert_eq!(sam.contains("cada"), true);
+        assert_eq!(sam.contains("abrac"), true);
+        assert_eq!(sam.contains("abc"), false);
+        assert_eq!(sam.count_occurrences("a"), 5);
+        assert_eq!(sam.count_occurrences("abra"), 2);
+        assert_eq!(sam.first_occurrence("bra"), Some(1));
+        assert_eq!(sam.first_occurrence("ra"), Some(2));
+        assert_eq!(sam.first_occurrence(""), Some(0));
+    }
+
+    #[test]
+    fn test_kth_substring() {
+        let text = "мисиссипи";
+        let sam = SuffixAutomaton::new(text);
+        let expected: Vec<String> = all_substrings(text).into_iter().collect();
+
+        assert_eq!(sam.distinct_substrings(), expected.len());
+
+        for (k, substring) in expected.into_iter().enumerate() {
+            assert_eq!(sam.kth_substring(k), Some(substring));
+        }
+    }
+
+    #[test]
+    fn test_online() {
+        let mut sam = SuffixAutomaton::default();
+        assert_eq!(sam.is_empty(), true);
+        assert_eq!(sam.distinct_substrings(), 0);
+        assert_eq!(sam.kth_substring(0), None);
+
+        sam.extend("abab");
+        assert_eq!(sam.count_occurrences("ab"), 2);
+
+        sam.push('a');
+        assert_eq!(sam.count_occurrences("ab"), 2);
+        assert_eq!(sam.count_occurrences("aba"), 2);
+        assert_eq!(sam.len(), 5);
+    }
+}
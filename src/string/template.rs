@@ -0,0 +1,500 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+use super::display_width;
+
+/// Largest width or precision of a format specifier, the limit of `format!`.
+const MAX_FORMAT: usize = u16::MAX as usize;
+
+/// The reason a template failed to parse or render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateErrorKind {
+    /// A `{` without a matching `}`, or with another `{` before it.
+    UnclosedPlaceholder,
+    /// A `}` that is neither closing a placeholder nor escaped as `}}`.
+    UnexpectedClosingBrace,
+    /// An empty key or key path segment, or one with chars other than
+    /// letters, digits and `_`.
+    InvalidKey,
+    /// A format specifier that is not `[[fill]align][width][.precision]`, or
+    /// whose width or precision is over 65535.
+    InvalidFormat,
+    /// A key that is not in the context, with [`MissingKey::Error`].
+    MissingKey,
+    /// A key whose value is a list or a map.
+    NotScalar,
+}
+
+/// An error of [`Template::parse`] or [`Template::render`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateError {
+    /// Byte offset in the template where the problem was found.
+    pub position: usize,
+    pub kind: TemplateErrorKind,
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self.kind {
+            TemplateErrorKind::UnclosedPlaceholder => "unclosed placeholder",
+            TemplateErrorKind::UnexpectedClosingBrace => "unexpected closing brace",
+            TemplateErrorKind::InvalidKey => "invalid key",
+            TemplateErrorKind::InvalidFormat => "invalid format specifier",
+            TemplateErrorKind::MissingKey => "missing key",
+            TemplateErrorKind::NotScalar => "value is not a scalar",
+        };
+
+        write!(f, "{} at position {}", message, self.position)
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// What [`Template::render`] does with a placeholder whose key is missing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingKey {
+    /// Fail with [`TemplateErrorKind::MissingKey`].
+    #[default]
+    Error,
+    /// Keep the placeholder as it's written in the template.
+    Keep,
+    /// Replace the placeholder with nothing.
+    Empty,
+}
+
+/// A value of the render context. Maps and lists are reached by key paths
+/// such as `user.name` or `items.0`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    List(Vec<Value>),
+    Map(HashMap<String, Value>),
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(value: i32) -> Self {
+        Value::Int(value.into())
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Int(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Float(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Str(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Str(value)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(value: Vec<T>) -> Self {
+        Value::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<HashMap<String, Value>> for Value {
+    fn from(value: HashMap<String, Value>) -> Self {
+        Value::Map(value)
+    }
+}
+
+impl<K: Into<String>, V: Into<Value>> FromIterator<(K, V)> for Value {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Value::Map(iter.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+}
+
+impl Value {
+    fn get(&self, segment: &str) -> Option<&Value> {
+        match self {
+            Value::Map(map) => map.get(segment),
+            Value::List(list) => list.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Spec {
+    fill: char,
+    align: Option<Align>,
+    width: usize,
+    precision: Option<usize>,
+}
+
+impl Spec {
+    fn parse(spec: &str) -> Option<Spec> {
+        let align_of = |c| match c {
+            '<' => Some(Align::Left),
+            '>' => Some(Align::Right),
+            '^' => Some(Align::Center),
+            _ => None,
+        };
+        let mut chars: Vec<char> = spec.chars().collect();
+        let mut result = Spec { fill: ' ', align: None, width: 0, precision: None };
+
+        if let Some(align) = chars.get(1).copied().and_then(align_of) {
+            result.fill = chars[0];
+            result.align = Some(align);
+            chars.drain(..2);
+        } else if let Some(align) = chars.first().copied().and_then(align_of) {
+            result.align = Some(align);
+            chars.remove(0);
+        }
+
+        let rest: String = chars.into_iter().collect();
+        let (width, precision) = match rest.split_once('.') {
+            Some((width, precision)) => (width, Some(precision)),
+            None => (rest.as_str(), None),
+        };
+
+        let number = |text: &str| {
+            let n: usize = text.parse().ok().filter(|_| text.bytes().all(|b| b.is_ascii_digit()))?;
+            Some(n).filter(|&n| n <= MAX_FORMAT)
+        };
+
+        if !width.is_empty() {
+            result.width = number(width)?;
+        }
+
+        if let Some(precision) = precision {
+            result.precision = Some(number(precision)?);
+        }
+
+        Some(result)
+    }
+
+    fn format(&self, value: &Value) -> Option<String> {
+        let text = match (value, self.precision) {
+            (Value::Bool(b), _) => b.to_string(),
+            (Value::Int(i), None | Some(0)) => i.to_string(),
+            (Value::Int(i), Some(precision)) => format!("{}.{}", i, "0".repeat(precision)),
+            (Value::Float(x), None) => x.to_string(),
+            (Value::Float(x), Some(precision)) => format!("{:.*}", precision, x),
+            (Value::Str(s), None) => s.clone(),
+            (Value::Str(s), Some(precision)) => s.chars().take(precision).collect(),
+            (Value::List(_) | Value::Map(_), _) => return None,
+        };
+
+        let numeric = matches!(value, Value::Int(_) | Value::Float(_));
+        let align = self.align.unwrap_or(if numeric { Align::Right } else { Align::Left });
+        let padding = self.width.saturating_sub(display_width(&text));
+        let (left, right) = match align {
+            Align::Left => (0, padding),
+            Align::Right => (padding, 0),
+            Align::Center => (padding / 2, padding - padding / 2),
+        };
+        let fill = |n| std::iter::repeat_n(self.fill, n);
+
+        Some(fill(left).chain(text.chars()).chain(fill(right)).collect())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field { path: Vec<String>, spec: Spec, span: Range<usize> },
+}
+
+/// A parsed template with `{key}` placeholders, rendered against a map of
+/// [`Value`]s.
+///
+/// A placeholder is `{path}` or `{path:spec}`: the path is keys joined by
+/// `.`, where keys of lists are indices, and the spec is
+/// `[[fill]align][width][.precision]` with `<`, `>` or `^` alignment.
+/// Numbers are right-aligned by default, the precision is the number of
+/// decimals for numbers and the maximum number of chars for strings, and the
+/// width is counted in terminal columns. Both are at most 65535. `{{` and
+/// `}}` are literal braces.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::{Template, Value};
+///
+/// let template = Template::parse("{user.name:<6}|{price:>8.2}|{{ok}}").unwrap();
+///
+/// let context: Value = [
+///     ("user", [("name", "Bob")].into_iter().collect()),
+///     ("price", Value::from(4.5)),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// assert_eq!(template.render(&context).unwrap(), "Bob   |    4.50|{ok}".to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    source: String,
+    segments: Vec<Segment>,
+    missing_key: MissingKey,
+}
+
+impl Template {
+    /// Parse the template, with the [`MissingKey::Error`] policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::{Template, TemplateErrorKind};
+    ///
+    /// let answer = Template::parse("Hello, {name").unwrap_err();
+    /// assert_eq!(answer.kind, TemplateErrorKind::UnclosedPlaceholder);
+    /// assert_eq!(answer.position, 7);
+    ///
+    /// let answer = Template::parse("{a..b}").unwrap_err();
+    /// assert_eq!(answer.kind, TemplateErrorKind::InvalidKey);
+    /// ```
+    pub fn parse(source: &str) -> Result<Template, TemplateError> {
+        let mut segments = vec![];
+        let mut literal = String::new();
+        let mut chars = source.char_indices().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '{' if chars.next_if(|&(_, c)| c == '{').is_some() => literal.push('{'),
+                '}' if chars.next_if(|&(_, c)| c == '}').is_some() => literal.push('}'),
+                '}' => return Err(TemplateError { position: i, kind: TemplateErrorKind::UnexpectedClosingBrace }),
+                '{' => {
+                    let unclosed = TemplateError { position: i, kind: TemplateErrorKind::UnclosedPlaceholder };
+                    let end = loop {
+                        match chars.next() {
+                            Some((j, '}')) => break j,
+                            Some((_, '{')) | None => return Err(unclosed),
+                            Some(_) => {}
+                        }
+                    };
+
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+
+                    segments.push(Template::parse_field(&source[i + 1..end], i..end + 1)?);
+                }
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Template { source: source.to_string(), segments, missing_key: MissingKey::default() })
+    }
+
+    fn parse_field(field: &str, span: Range<usize>) -> Result<Segment, TemplateError> {
+        let (key, spec) = match field.split_once(':') {
+            Some((key, spec)) => (key, Some(spec)),
+            None => (field, None),
+        };
+
+        let path: Vec<String> = key.split('.').map(str::to_string).collect();
+        let is_valid = |segment: &String| {
+            !segment.is_empty() && segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+        };
+
+        if !path.iter().all(is_valid) {
+            return Err(TemplateError { position: span.start + 1, kind: TemplateErrorKind::InvalidKey });
+        }
+
+        let spec = match spec {
+            Some(spec) => Spec::parse(spec).ok_or(TemplateError {
+                position: span.start + key.len() + 2,
+                kind: TemplateErrorKind::InvalidFormat,
+            })?,
+            None => Spec { fill: ' ', align: None, width: 0, precision: None },
+        };
+
+        Ok(Segment::Field { path, spec, span })
+    }
+
+    /// Set what rendering does with missing keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::{MissingKey, Template, Value};
+    ///
+    /// let context: Value = [("a", 1)].into_iter().collect();
+    ///
+    /// let template = Template::parse("{a} {b:>3}").unwrap().missing_key(MissingKey::Keep);
+    /// assert_eq!(template.render(&context).unwrap(), "1 {b:>3}".to_string());
+    ///
+    /// let template = template.missing_key(MissingKey::Empty);
+    /// assert_eq!(template.render(&context).unwrap(), "1 ".to_string());
+    /// ```
+    pub fn missing_key(mut self, policy: MissingKey) -> Self {
+        self.missing_key = policy;
+        self
+    }
+
+    /// Return the key paths of the placeholders in order of appearance.
+    pub fn keys(&self) -> Vec<String> {
+        self.segments
+            .iter()
+            .filter_map(|segment| match segment {
+                Segment::Field { path, .. } => Some(path.join(".")),
+                Segment::Literal(_) => None,
+            })
+            .collect()
+    }
+
+    /// Render the template, looking up the key paths in the context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::{Template, TemplateErrorKind, Value};
+    ///
+    /// let template = Template::parse("{items.1:^7}").unwrap();
+    ///
+    /// let context: Value = [("items", vec!["один", "два"])].into_iter().collect();
+    /// assert_eq!(template.render(&context).unwrap(), "  два  ".to_string());
+    ///
+    /// let context: Value = [("items", vec!["один"])].into_iter().collect();
+    /// assert_eq!(template.render(&context).unwrap_err().kind, TemplateErrorKind::MissingKey);
+    /// ```
+    pub fn render(&self, context: &Value) -> Result<String, TemplateError> {
+        let mut result = String::new();
+
+        for segment in &self.segments {
+            let (path, spec, span) = match segment {
+                Segment::Literal(literal) => {
+                    result.push_str(literal);
+                    continue;
+                }
+                Segment::Field { path, spec, span } => (path, spec, span),
+            };
+
+            let value = path.iter().try_fold(context, |value, segment| value.get(segment));
+
+            match value {
+                Some(value) => {
+                    let text = spec
+                        .format(value)
+                        .ok_or(TemplateError { position: span.start, kind: TemplateErrorKind::NotScalar })?;
+                    result.push_str(&text);
+                }
+                None => match self.missing_key {
+                    MissingKey::Error => {
+                        return Err(TemplateError { position: span.start, kind: TemplateErrorKind::MissingKey })
+                    }
+                    MissingKey::Keep => result.push_str(&self.source[span.clone()]),
+                    MissingKey::Empty => {}
+                },
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> Value {
+        [
+            ("name", Value::from("Алиса")),
+            ("age", Value::from(30)),
+            ("pi", Value::from(std::f64::consts::PI)),
+            ("tags", Value::from(vec!["a", "b"])),
+            ("city", "東京".into()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |source| Template::parse(source).unwrap_err();
+
+        assert_eq!(error("a}b"), TemplateError { position: 1, kind: TemplateErrorKind::UnexpectedClosingBrace });
+        assert_eq!(error("{a{b}}"), TemplateError { position: 0, kind: TemplateErrorKind::UnclosedPlaceholder });
+        assert_eq!(error("x{}"), TemplateError { position: 2, kind: TemplateErrorKind::InvalidKey });
+        assert_eq!(error("{a b}"), TemplateError { position: 1, kind: TemplateErrorKind::InvalidKey });
+        assert_eq!(error("{a:>x}"), TemplateError { position: 3, kind: TemplateErrorKind::InvalidFormat });
+        assert_eq!(error("{a:5.}"), TemplateError { position: 3, kind: TemplateErrorKind::InvalidFormat });
+        assert_eq!(error("{n:.18446744073709551615}").kind, TemplateErrorKind::InvalidFormat);
+        assert_eq!(error("{n:18446744073709551615}").kind, TemplateErrorKind::InvalidFormat);
+        assert_eq!(error("{n:99999999999999999999}").kind, TemplateErrorKind::InvalidFormat);
+        assert_eq!(error("{n:.70000}").kind, TemplateErrorKind::InvalidFormat);
+        assert_eq!(error("{n:65536}").kind, TemplateErrorKind::InvalidFormat);
+    }
+
+    #[test]
+    fn test_render() {
+        let render = |source| Template::parse(source).unwrap().render(&context());
+
+        assert_eq!(render(""), Ok("".to_string()));
+        assert_eq!(render("{{{name}}}"), Ok("{Алиса}".to_string()));
+        assert_eq!(render("[{name:*^9}]"), Ok("[**Алиса**]".to_string()));
+        assert_eq!(render("[{age:5}][{age:<5}][{age:.1}]"), Ok("[   30][30   ][30.0]".to_string()));
+        assert_eq!(render("{pi:.3} {name:.2}"), Ok("3.142 Ал".to_string()));
+        assert_eq!(render("[{city:6}]"), Ok("[東京  ]".to_string()));
+        assert_eq!(render("{tags.0}{tags.1}"), Ok("ab".to_string()));
+        assert_eq!(render("{tags}"), Err(TemplateError { position: 0, kind: TemplateErrorKind::NotScalar }));
+        assert_eq!(render("{name.first}"), Err(TemplateError { position: 0, kind: TemplateErrorKind::MissingKey }));
+    }
+
+    #[test]
+    fn test_render_int_precision() {
+        let render = |source, n: i64| {
+            let context: Value = [("n", Value::from(n))].into_iter().collect();
+            Template::parse(source).unwrap().render(&context)
+        };
+
+        assert_eq!(render("{n:.0}", -7), Ok("-7".to_string()));
+        assert_eq!(render("{n:.2}", -7), Ok("-7.00".to_string()));
+        assert_eq!(render("{n:.1}", 9_007_199_254_740_993), Ok("9007199254740993.0".to_string()));
+        assert_eq!(render("{n:>8.3}", i64::MIN), Ok("-9223372036854775808.000".to_string()));
+        assert_eq!(render("{n:.65535}", 1).map(|text| text.len()), Ok(65537));
+    }
+
+    #[test]
+    fn test_render_max_format() {
+        let context: Value = [("x", Value::from(0.5)), ("s", Value::from("ab"))].into_iter().collect();
+        let render = |source| Template::parse(source).unwrap().render(&context);
+
+        assert_eq!(render("{x:.65535}").map(|text| text.len()), Ok(65537));
+        assert_eq!(render("{s:65535}").map(|text| text.len()), Ok(65535));
+    }
+
+    #[test]
+    fn test_keys() {
+        let template = Template::parse("{a.b} {{c}} {d:>4}").unwrap();
+        assert_eq!(template.keys(), vec!["a.b".to_string(), "d".to_string()]);
+    }
+}
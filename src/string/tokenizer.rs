@@ -0,0 +1,618 @@
+//! Word and grapheme cluster segmentation following Unicode Standard Annex #29
+//! for Unicode 15.1, including the Indic conjunct rule GB9c.
+//!
+//! The property tables are compact and cover the scripts in common use: Latin,
+//! Cyrillic, Greek, Hebrew, Arabic, the Indic scripts, CJK, Hangul and emoji.
+//! Characters from rarer scripts fall back to their `char` classification.
+//! GB9c treats every Extend char and the Zwj as `InCB=Extend`.
+
+/// The unit in which the length of a piece of text is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextUnit {
+    /// Unicode scalar values, as produced by `str::chars`.
+    Chars,
+    /// Extended grapheme clusters, i.e. user-perceived characters.
+    Graphemes,
+    /// Terminal columns, see [`crate::string::display_width`].
+    Width,
+}
+
+impl TextUnit {
+    /// Return the length of the text in this unit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gutils::string::TextUnit;
+    ///
+    /// let answer = TextUnit::Chars.count("e\u{301}te\u{301}");
+    /// assert_eq!(answer, 5);
+    ///
+    /// let answer = TextUnit::Graphemes.count("e\u{301}te\u{301}");
+    /// assert_eq!(answer, 3);
+    /// ```
+    pub fn count(self, s: &str) -> usize {
+        match self {
+            TextUnit::Chars => s.chars().count(),
+            TextUnit::Graphemes => grapheme_bounds(s).len().saturating_sub(1),
+            TextUnit::Width => super::display_width(s),
+        }
+    }
+}
+
+pub(super) type Table = &'static [(u32, u32)];
+
+pub(super) fn in_table(table: Table, c: char) -> bool {
+    let c = c as u32;
+
+    table
+        .binary_search_by(|&(lo, hi)| {
+            if hi < c {
+                std::cmp::Ordering::Less
+            } else if lo > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+const EXTEND: Table = &[
+    (0x0300, 0x036F), (0x0483, 0x0489), (0x0591, 0x05BD), (0x05BF, 0x05BF),
+    (0x05C1, 0x05C2), (0x05C4, 0x05C5), (0x05C7, 0x05C7), (0x0610, 0x061A),
+    (0x064B, 0x065F), (0x0670, 0x0670), (0x06D6, 0x06DC), (0x06DF, 0x06E4),
+    (0x06E7, 0x06E8), (0x06EA, 0x06ED), (0x0711, 0x0711), (0x0730, 0x074A),
+    (0x07A6, 0x07B0), (0x07EB, 0x07F3), (0x0816, 0x082D), (0x0859, 0x085B),
+    (0x08D3, 0x08E1), (0x08E3, 0x0902), (0x093A, 0x093A), (0x093C, 0x093C),
+    (0x0941, 0x0948), (0x094D, 0x094D), (0x0951, 0x0957), (0x0962, 0x0963),
+    (0x0981, 0x0981), (0x09BC, 0x09BC), (0x09BE, 0x09BE), (0x09C1, 0x09C4),
+    (0x09CD, 0x09CD), (0x09D7, 0x09D7), (0x09E2, 0x09E3), (0x0A01, 0x0A02),
+    (0x0A3C, 0x0A3C), (0x0A41, 0x0A51), (0x0A70, 0x0A71), (0x0A75, 0x0A75),
+    (0x0A81, 0x0A82), (0x0ABC, 0x0ABC), (0x0AC1, 0x0AC8), (0x0ACD, 0x0ACD),
+    (0x0AE2, 0x0AE3), (0x0B01, 0x0B01), (0x0B3C, 0x0B3C), (0x0B3E, 0x0B3F),
+    (0x0B41, 0x0B44), (0x0B4D, 0x0B4D), (0x0B56, 0x0B57), (0x0B62, 0x0B63),
+    (0x0BBE, 0x0BBE), (0x0BC0, 0x0BC0), (0x0BCD, 0x0BCD), (0x0BD7, 0x0BD7),
+    (0x0C00, 0x0C00), (0x0C3E, 0x0C40), (0x0C46, 0x0C56), (0x0C62, 0x0C63),
+    (0x0CBC, 0x0CBC), (0x0CBF, 0x0CBF), (0x0CC2, 0x0CC2), (0x0CC6, 0x0CC6),
+    (0x0CCC, 0x0CCD), (0x0CD5, 0x0CD6), (0x0D00, 0x0D01), (0x0D3B, 0x0D3C),
+    (0x0D3E, 0x0D3E), (0x0D41, 0x0D44), (0x0D4D, 0x0D4D), (0x0D57, 0x0D57),
+    (0x0D62, 0x0D63), (0x0DCA, 0x0DCA), (0x0DCF, 0x0DCF), (0x0DD2, 0x0DD6),
+    (0x0DDF, 0x0DDF), (0x0E31, 0x0E31), (0x0E34, 0x0E3A), (0x0E47, 0x0E4E),
+    (0x0EB1, 0x0EB1), (0x0EB4, 0x0EBC), (0x0EC8, 0x0ECD), (0x0F18, 0x0F19),
+    (0x0F35, 0x0F35), (0x0F37, 0x0F37), (0x0F39, 0x0F39), (0x0F71, 0x0F7E),
+    (0x0F80, 0x0F84), (0x0F86, 0x0F87), (0x0F8D, 0x0FBC), (0x0FC6, 0x0FC6),
+    (0x102D, 0x1030), (0x1032, 0x1037), (0x1039, 0x103A), (0x103D, 0x103E),
+    (0x1058, 0x1059), (0x105E, 0x1060), (0x1071, 0x1074), (0x1082, 0x1082),
+    (0x1085, 0x1086), (0x108D, 0x108D), (0x109D, 0x109D), (0x135D, 0x135F),
+    (0x1712, 0x1714), (0x1732, 0x1734), (0x1752, 0x1753), (0x1772, 0x1773),
+    (0x17B4, 0x17B5), (0x17B7, 0x17BD), (0x17C6, 0x17C6), (0x17C9, 0x17D3),
+    (0x17DD, 0x17DD), (0x180B, 0x180D), (0x1885, 0x1886), (0x18A9, 0x18A9),
+    (0x1920, 0x1922), (0x1927, 0x1928), (0x1932, 0x1932), (0x1939, 0x193B),
+    (0x1A17, 0x1A18), (0x1A1B, 0x1A1B), (0x1A56, 0x1A56), (0x1A58, 0x1A60),
+    (0x1A62, 0x1A62), (0x1A65, 0x1A6C), (0x1A73, 0x1A7F), (0x1AB0, 0x1AFF),
+    (0x1B00, 0x1B03), (0x1B34, 0x1B3A), (0x1B3C, 0x1B3C), (0x1B42, 0x1B42),
+    (0x1B6B, 0x1B73), (0x1B80, 0x1B81), (0x1BA2, 0x1BA5), (0x1BA8, 0x1BA9),
+    (0x1BAB, 0x1BAD), (0x1BE6, 0x1BE6), (0x1BE8, 0x1BE9), (0x1BED, 0x1BED),
+    (0x1BEF, 0x1BF1), (0x1C2C, 0x1C33), (0x1C36, 0x1C37), (0x1CD0, 0x1CD2),
+    (0x1CD4, 0x1CE0), (0x1CE2, 0x1CE8), (0x1CED, 0x1CED), (0x1CF4, 0x1CF4),
+    (0x1CF8, 0x1CF9), (0x1DC0, 0x1DFF), (0x200C, 0x200C), (0x20D0, 0x20F0),
+    (0x2CEF, 0x2CF1), (0x2D7F, 0x2D7F), (0x2DE0, 0x2DFF), (0x302A, 0x302F),
+    (0x3099, 0x309A), (0xA66F, 0xA672), (0xA674, 0xA67D), (0xA69E, 0xA69F),
+    (0xA6F0, 0xA6F1), (0xA802, 0xA802), (0xA806, 0xA806), (0xA80B, 0xA80B),
+    (0xA825, 0xA826), (0xA8C4, 0xA8C5), (0xA8E0, 0xA8F1), (0xA8FF, 0xA8FF),
+    (0xA926, 0xA92D), (0xA947, 0xA951), (0xA980, 0xA982), (0xA9B3, 0xA9B3),
+    (0xA9B6, 0xA9B9), (0xA9BC, 0xA9BD), (0xA9E5, 0xA9E5), (0xAA29, 0xAA2E),
+    (0xAA31, 0xAA32), (0xAA35, 0xAA36), (0xAA43, 0xAA43), (0xAA4C, 0xAA4C),
+    (0xAA7C, 0xAA7C), (0xAAB0, 0xAAB0), (0xAAB2, 0xAAB4), (0xAAB7, 0xAAB8),
+    (0xAABE, 0xAABF), (0xAAC1, 0xAAC1), (0xAAEC, 0xAAED), (0xAAF6, 0xAAF6),
+    (0xABE5, 0xABE5), (0xABE8, 0xABE8), (0xABED, 0xABED), (0xFB1E, 0xFB1E),
+    (0xFE00, 0xFE0F), (0xFE20, 0xFE2F), (0xFF9E, 0xFF9F), (0x101FD, 0x101FD),
+    (0x102E0, 0x102E0), (0x10376, 0x1037A), (0x10A01, 0x10A0F), (0x10A38, 0x10A3F),
+    (0x11001, 0x11001), (0x11038, 0x11046), (0x1107F, 0x11081), (0x110B3, 0x110B6),
+    (0x110B9, 0x110BA), (0x11100, 0x11102), (0x11127, 0x11134), (0x1D165, 0x1D165),
+    (0x1D167, 0x1D169), (0x1D16E, 0x1D172), (0x1D17B, 0x1D182), (0x1D185, 0x1D18B),
+    (0x1D1AA, 0x1D1AD), (0x1E000, 0x1E02A), (0x1E8D0, 0x1E8D6), (0x1E944, 0x1E94A),
+    (0x1F3FB, 0x1F3FF), (0xE0020, 0xE007F), (0xE0100, 0xE01EF),
+];
+
+const SPACING_MARK: Table = &[
+    (0x0903, 0x0903), (0x093B, 0x093B), (0x093E, 0x0940), (0x0949, 0x094C),
+    (0x094E, 0x094F), (0x0982, 0x0983), (0x09BF, 0x09C0), (0x09C7, 0x09C8),
+    (0x09CB, 0x09CC), (0x0A03, 0x0A03), (0x0A3E, 0x0A40), (0x0A83, 0x0A83),
+    (0x0ABE, 0x0AC0), (0x0AC9, 0x0AC9), (0x0ACB, 0x0ACC), (0x0B02, 0x0B03),
+    (0x0B40, 0x0B40), (0x0B47, 0x0B48), (0x0B4B, 0x0B4C), (0x0BBF, 0x0BBF),
+    (0x0BC1, 0x0BC2), (0x0BC6, 0x0BC8), (0x0BCA, 0x0BCC), (0x0C01, 0x0C03),
+    (0x0C41, 0x0C44), (0x0C82, 0x0C83), (0x0CBE, 0x0CBE), (0x0CC0, 0x0CC1),
+    (0x0CC3, 0x0CC4), (0x0CC7, 0x0CC8), (0x0CCA, 0x0CCB), (0x0D02, 0x0D03),
+    (0x0D3F, 0x0D40), (0x0D46, 0x0D48), (0x0D4A, 0x0D4C), (0x0D82, 0x0D83),
+    (0x0DD0, 0x0DD1), (0x0DD8, 0x0DDE), (0x0DF2, 0x0DF3), (0x0E33, 0x0E33),
+    (0x0EB3, 0x0EB3), (0x0F3E, 0x0F3F), (0x0F7F, 0x0F7F), (0x1031, 0x1031),
+    (0x103B, 0x103C), (0x1056, 0x1057), (0x1084, 0x1084), (0x17B6, 0x17B6),
+    (0x17BE, 0x17C5), (0x17C7, 0x17C8), (0x1923, 0x1926), (0x1929, 0x192B),
+    (0x1930, 0x1931), (0x1933, 0x1938), (0x1A19, 0x1A1A), (0x1A55, 0x1A55),
+    (0x1A57, 0x1A57), (0x1A6D, 0x1A72), (0x1B04, 0x1B04), (0x1B3B, 0x1B3B),
+    (0x1B3D, 0x1B41), (0x1B43, 0x1B44), (0x1B82, 0x1B82), (0x1BA1, 0x1BA1),
+    (0x1BA6, 0x1BA7), (0x1BAA, 0x1BAA), (0x1BE7, 0x1BE7), (0x1BEA, 0x1BEC),
+    (0x1BEE, 0x1BEE), (0x1BF2, 0x1BF3), (0x1C24, 0x1C2B), (0x1C34, 0x1C35),
+    (0x1CE1, 0x1CE1), (0x1CF7, 0x1CF7), (0xA823, 0xA824), (0xA827, 0xA827),
+    (0xA880, 0xA881), (0xA8B4, 0xA8C3), (0xA952, 0xA953), (0xA983, 0xA983),
+    (0xA9B4, 0xA9B5), (0xA9BA, 0xA9BB), (0xA9BE, 0xA9C0), (0xAA2F, 0xAA30),
+    (0xAA33, 0xAA34), (0xAA4D, 0xAA4D), (0xAAEB, 0xAAEB), (0xAAEE, 0xAAEF),
+    (0xAAF5, 0xAAF5), (0xABE3, 0xABE4), (0xABE6, 0xABE7), (0xABE9, 0xABEA),
+    (0xABEC, 0xABEC),
+];
+
+const PREPEND: Table = &[
+    (0x0600, 0x0605), (0x06DD, 0x06DD), (0x070F, 0x070F), (0x08E2, 0x08E2),
+    (0x0D4E, 0x0D4E), (0x110BD, 0x110BD), (0x111C2, 0x111C3),
+];
+
+const EXTENDED_PICTOGRAPHIC: Table = &[
+    (0x00A9, 0x00A9), (0x00AE, 0x00AE), (0x203C, 0x203C), (0x2049, 0x2049),
+    (0x2122, 0x2122), (0x2139, 0x2139), (0x2194, 0x2199), (0x21A9, 0x21AA),
+    (0x231A, 0x231B), (0x2328, 0x2328), (0x2388, 0x2388), (0x23CF, 0x23CF),
+    (0x23E9, 0x23F3), (0x23F8, 0x23FA), (0x24C2, 0x24C2), (0x25AA, 0x25AB),
+    (0x25B6, 0x25B6), (0x25C0, 0x25C0), (0x25FB, 0x25FE), (0x2600, 0x27BF),
+    (0x2934, 0x2935), (0x2B05, 0x2B07), (0x2B1B, 0x2B1C), (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55), (0x3030, 0x3030), (0x303D, 0x303D), (0x3297, 0x3297),
+    (0x3299, 0x3299), (0x1F000, 0x1F0FF), (0x1F10D, 0x1F10F), (0x1F12F, 0x1F12F),
+    (0x1F16C, 0x1F171), (0x1F17E, 0x1F17F), (0x1F18E, 0x1F18E), (0x1F191, 0x1F19A),
+    (0x1F1AD, 0x1F1E5), (0x1F201, 0x1F20F), (0x1F21A, 0x1F21A), (0x1F22F, 0x1F22F),
+    (0x1F232, 0x1F23A), (0x1F23C, 0x1F23F), (0x1F249, 0x1F3FA), (0x1F400, 0x1F53D),
+    (0x1F546, 0x1F64F), (0x1F680, 0x1F6FF), (0x1F774, 0x1F77F), (0x1F7D5, 0x1F7FF),
+    (0x1F80C, 0x1F80F), (0x1F848, 0x1F84F), (0x1F85A, 0x1F85F), (0x1F888, 0x1F88F),
+    (0x1F8AE, 0x1F8FF), (0x1F90C, 0x1F93A), (0x1F93C, 0x1F945), (0x1F947, 0x1FAFF),
+    (0x1FC00, 0x1FFFD),
+];
+
+const FORMAT: Table = &[
+    (0x00AD, 0x00AD), (0x0600, 0x0605), (0x061C, 0x061C), (0x06DD, 0x06DD),
+    (0x070F, 0x070F), (0x08E2, 0x08E2), (0x180E, 0x180E), (0x200E, 0x200F),
+    (0x202A, 0x202E), (0x2060, 0x2064), (0x2066, 0x206F), (0xFEFF, 0xFEFF),
+    (0xFFF9, 0xFFFB), (0xE0001, 0xE0001),
+];
+
+const KATAKANA: Table = &[
+    (0x3031, 0x3035), (0x309B, 0x309C), (0x30A0, 0x30FA), (0x30FC, 0x30FF),
+    (0x31F0, 0x31FF), (0x32D0, 0x32FE), (0x3300, 0x3357), (0xFF66, 0xFF9D),
+    (0x1B000, 0x1B000),
+];
+
+const HEBREW_LETTER: Table = &[
+    (0x05D0, 0x05EA), (0x05EF, 0x05F2), (0xFB1D, 0xFB1D), (0xFB1F, 0xFB28),
+    (0xFB2A, 0xFB4F),
+];
+
+/// Letters that UAX #29 does not join into words: ideographs, kana and the
+/// scripts written without spaces, which need dictionary-based segmentation.
+const NON_WORD_LETTER: Table = &[
+    (0x0E00, 0x0EFF), (0x1000, 0x109F), (0x1780, 0x17FF), (0x1950, 0x19DF),
+    (0x1A20, 0x1AAF), (0x3005, 0x3007), (0x3040, 0x309F), (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF), (0xAA60, 0xAADF), (0xF900, 0xFAFF), (0x20000, 0x3FFFF),
+];
+
+const MID_NUM_LET: Table = &[
+    (0x002E, 0x002E), (0x2018, 0x2019), (0x2024, 0x2024), (0xFE52, 0xFE52),
+    (0xFF07, 0xFF07), (0xFF0E, 0xFF0E),
+];
+
+const MID_LETTER: Table = &[
+    (0x003A, 0x003A), (0x00B7, 0x00B7), (0x0387, 0x0387), (0x055F, 0x055F),
+    (0x05F4, 0x05F4), (0x2027, 0x2027), (0xFE13, 0xFE13), (0xFE55, 0xFE55),
+    (0xFF1A, 0xFF1A),
+];
+
+const MID_NUM: Table = &[
+    (0x002C, 0x002C), (0x003B, 0x003B), (0x037E, 0x037E), (0x0589, 0x0589),
+    (0x060C, 0x060D), (0x066C, 0x066C), (0x07F8, 0x07F8), (0x2044, 0x2044),
+    (0xFE10, 0xFE10), (0xFE14, 0xFE14), (0xFE50, 0xFE50), (0xFE54, 0xFE54),
+    (0xFF0C, 0xFF0C), (0xFF1B, 0xFF1B),
+];
+
+const EXTEND_NUM_LET: Table = &[
+    (0x005F, 0x005F), (0x202F, 0x202F), (0x203F, 0x2040), (0x2054, 0x2054),
+    (0xFE33, 0xFE34), (0xFE4D, 0xFE4F), (0xFF3F, 0xFF3F),
+];
+
+const WSEG_SPACE: Table = &[
+    (0x0020, 0x0020), (0x1680, 0x1680), (0x2000, 0x2006), (0x2008, 0x200A),
+    (0x205F, 0x205F), (0x3000, 0x3000),
+];
+
+/// The consonants that GB9c joins into conjuncts, `InCB=Consonant`.
+const INCB_CONSONANT: Table = &[
+    (0x0915, 0x0939), (0x0958, 0x095F), (0x0978, 0x097F), (0x0995, 0x09A8),
+    (0x09AA, 0x09B0), (0x09B2, 0x09B2), (0x09B6, 0x09B9), (0x09DC, 0x09DD),
+    (0x09DF, 0x09DF), (0x09F0, 0x09F1), (0x0A95, 0x0AA8), (0x0AAA, 0x0AB0),
+    (0x0AB2, 0x0AB3), (0x0AB5, 0x0AB9), (0x0AF9, 0x0AF9), (0x0B15, 0x0B28),
+    (0x0B2A, 0x0B30), (0x0B32, 0x0B33), (0x0B35, 0x0B39), (0x0B5C, 0x0B5D),
+    (0x0B5F, 0x0B5F), (0x0B71, 0x0B71), (0x0C15, 0x0C28), (0x0C2A, 0x0C39),
+    (0x0C58, 0x0C5A), (0x0D15, 0x0D3A),
+];
+
+/// The viramas that link consonants into conjuncts, `InCB=Linker`.
+fn is_incb_linker(c: char) -> bool {
+    matches!(c, '\u{094D}' | '\u{09CD}' | '\u{0ACD}' | '\u{0B4D}' | '\u{0C4D}' | '\u{0D4D}')
+}
+
+pub(super) fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Grapheme {
+    CR,
+    LF,
+    Control,
+    Extend,
+    Zwj,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    Lv,
+    Lvt,
+    Other,
+}
+
+fn grapheme_property(c: char) -> Grapheme {
+    match c {
+        '\r' => Grapheme::CR,
+        '\n' => Grapheme::LF,
+        '\u{200D}' => Grapheme::Zwj,
+        '\u{1100}'..='\u{115F}' | '\u{A960}'..='\u{A97C}' => Grapheme::L,
+        '\u{1160}'..='\u{11A7}' | '\u{D7B0}'..='\u{D7C6}' => Grapheme::V,
+        '\u{11A8}'..='\u{11FF}' | '\u{D7CB}'..='\u{D7FB}' => Grapheme::T,
+        '\u{AC00}'..='\u{D7A3}' if (c as u32 - 0xAC00).is_multiple_of(28) => Grapheme::Lv,
+        '\u{AC00}'..='\u{D7A3}' => Grapheme::Lvt,
+        c if is_regional_indicator(c) => Grapheme::RegionalIndicator,
+        c if in_table(EXTEND, c) => Grapheme::Extend,
+        c if in_table(SPACING_MARK, c) => Grapheme::SpacingMark,
+        c if in_table(PREPEND, c) => Grapheme::Prepend,
+        c if c.is_control() || in_table(FORMAT, c) || matches!(c, '\u{200B}' | '\u{2028}' | '\u{2029}') => {
+            Grapheme::Control
+        }
+        _ => Grapheme::Other,
+    }
+}
+
+/// Return whether true or false if the char takes no column on its own:
+/// combining marks, joiners, format and control chars and Hangul medial jamo.
+pub(super) fn is_zero_width(c: char) -> bool {
+    matches!(
+        grapheme_property(c),
+        Grapheme::Extend | Grapheme::Zwj | Grapheme::Control | Grapheme::CR | Grapheme::LF | Grapheme::V | Grapheme::T
+    )
+}
+
+/// Return the byte offsets of every extended grapheme cluster boundary,
+/// including `0` and `s.len()`.
+fn grapheme_bounds(s: &str) -> Vec<usize> {
+    let mut bounds = vec![0];
+    let mut chars = s.char_indices();
+    let Some((_, first)) = chars.next() else {
+        return bounds;
+    };
+
+    let mut prev = grapheme_property(first);
+    // GB11: inside `ExtPict Extend*` and possibly followed by a Zwj.
+    let mut in_emoji = in_table(EXTENDED_PICTOGRAPHIC, first);
+    let mut emoji_zwj = false;
+    let mut regional_count = usize::from(prev == Grapheme::RegionalIndicator);
+    // GB9c: inside `Consonant [Extend Linker]*`, and whether a Linker was seen.
+    let mut in_conjunct = in_table(INCB_CONSONANT, first);
+    let mut conjunct_linked = false;
+
+    for (idx, c) in chars {
+        use Grapheme::*;
+
+        let next = grapheme_property(c);
+        let is_pictographic = in_table(EXTENDED_PICTOGRAPHIC, c);
+        let is_consonant = in_table(INCB_CONSONANT, c);
+
+        let join = match (prev, next) {
+            (CR, LF) => true,
+            (CR | LF | Control, _) | (_, CR | LF | Control) => false,
+            (L, L | V | Lv | Lvt) | (Lv | V, V | T) | (Lvt | T, T) => true,
+            (_, Extend | Zwj | SpacingMark) | (Prepend, _) => true,
+            (Zwj, _) if emoji_zwj && is_pictographic => true,
+            _ if conjunct_linked && is_consonant => true,
+            (RegionalIndicator, RegionalIndicator) => regional_count % 2 == 1,
+            _ => false,
+        };
+
+        if !join {
+            bounds.push(idx);
+        }
+
+        emoji_zwj = in_emoji && next == Zwj;
+        in_emoji = is_pictographic || (in_emoji && next == Extend);
+        conjunct_linked = if is_consonant {
+            false
+        } else {
+            in_conjunct && (conjunct_linked || is_incb_linker(c))
+        };
+        in_conjunct = is_consonant || (in_conjunct && matches!(next, Extend | Zwj));
+        regional_count = if next == RegionalIndicator { regional_count + 1 } else { 0 };
+        prev = next;
+    }
+
+    bounds.push(s.len());
+    bounds
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Word {
+    CR,
+    LF,
+    Newline,
+    Extend,
+    Zwj,
+    RegionalIndicator,
+    Format,
+    Katakana,
+    HebrewLetter,
+    ALetter,
+    SingleQuote,
+    DoubleQuote,
+    MidNumLet,
+    MidLetter,
+    MidNum,
+    Numeric,
+    ExtendNumLet,
+    WSegSpace,
+    Other,
+}
+
+impl Word {
+    fn is_ignorable(self) -> bool {
+        matches!(self, Word::Extend | Word::Format | Word::Zwj)
+    }
+
+    fn is_ah_letter(self) -> bool {
+        matches!(self, Word::ALetter | Word::HebrewLetter)
+    }
+
+    fn is_mid_letter_q(self) -> bool {
+        matches!(self, Word::MidLetter | Word::MidNumLet | Word::SingleQuote)
+    }
+
+    fn is_mid_num_q(self) -> bool {
+        matches!(self, Word::MidNum | Word::MidNumLet | Word::SingleQuote)
+    }
+}
+
+fn word_property(c: char) -> Word {
+    match c {
+        '\r' => Word::CR,
+        '\n' => Word::LF,
+        '\u{000B}' | '\u{000C}' | '\u{0085}' | '\u{2028}' | '\u{2029}' => Word::Newline,
+        '\u{200D}' => Word::Zwj,
+        '\'' => Word::SingleQuote,
+        '"' => Word::DoubleQuote,
+        c if is_regional_indicator(c) => Word::RegionalIndicator,
+        c if in_table(EXTEND, c) || in_table(SPACING_MARK, c) => Word::Extend,
+        c if in_table(FORMAT, c) => Word::Format,
+        c if in_table(KATAKANA, c) => Word::Katakana,
+        c if in_table(HEBREW_LETTER, c) => Word::HebrewLetter,
+        c if in_table(MID_NUM_LET, c) => Word::MidNumLet,
+        c if in_table(MID_LETTER, c) => Word::MidLetter,
+        c if in_table(MID_NUM, c) => Word::MidNum,
+        c if in_table(EXTEND_NUM_LET, c) => Word::ExtendNumLet,
+        c if in_table(WSEG_SPACE, c) => Word::WSegSpace,
+        c if c.is_numeric() && !c.is_alphabetic() => Word::Numeric,
+        c if c.is_alphabetic() && !in_table(NON_WORD_LETTER, c) => Word::ALetter,
+        _ => Word::Other,
+    }
+}
+
+/// Return the byte offsets of every word boundary, including `0` and `s.len()`.
+fn word_bounds(s: &str) -> Vec<usize> {
+    use Word::*;
+
+    let chars: Vec<(usize, char)> = s.char_indices().collect();
+    let props: Vec<Word> = chars.iter().map(|&(_, c)| word_property(c)).collect();
+    let mut bounds = vec![0];
+
+    // WB4: `solid_after[i]` is the first property from `i` on that is not
+    // Extend, Format or Zwj.
+    let mut solid_after = vec![None; props.len() + 1];
+
+    for i in (0..props.len()).rev() {
+        solid_after[i] = if props[i].is_ignorable() { solid_after[i + 1] } else { Some(props[i]) };
+    }
+
+    // WB4: the last two properties so far that are not Extend, Format or Zwj,
+    // and the length of the run of regional indicators ending with them.
+    let mut solid_left: Option<Word> = None;
+    let mut far_left: Option<Word> = None;
+    let mut regional_count = 0;
+
+    for i in 0..chars.len() {
+        let right = props[i];
+
+        if i > 0 {
+            let left = props[i - 1];
+
+            let join = if left == CR && right == LF {
+                true
+            } else if matches!(left, CR | LF | Newline) || matches!(right, CR | LF | Newline) {
+                false
+            } else if (left == Zwj && in_table(EXTENDED_PICTOGRAPHIC, chars[i].1))
+                || (left == WSegSpace && right == WSegSpace)
+                || right.is_ignorable()
+            {
+                true
+            } else {
+                let left = solid_left.unwrap_or(left);
+                let far_right = solid_after[i + 1];
+
+                match (left, right) {
+                    (l, r) if l.is_ah_letter() && r.is_ah_letter() => true,
+                    (l, r) if l.is_ah_letter() && r.is_mid_letter_q() => far_right.is_some_and(Word::is_ah_letter),
+                    (l, r) if l.is_mid_letter_q() && r.is_ah_letter() => far_left.is_some_and(Word::is_ah_letter),
+                    (HebrewLetter, SingleQuote) => true,
+                    (HebrewLetter, DoubleQuote) => far_right == Some(HebrewLetter),
+                    (DoubleQuote, HebrewLetter) => far_left == Some(HebrewLetter),
+                    (Numeric, Numeric) => true,
+                    (l, Numeric) if l.is_ah_letter() => true,
+                    (Numeric, r) if r.is_ah_letter() => true,
+                    (l, Numeric) if l.is_mid_num_q() => far_left == Some(Numeric),
+                    (Numeric, r) if r.is_mid_num_q() => far_right == Some(Numeric),
+                    (Katakana, Katakana) => true,
+                    (ALetter | HebrewLetter | Numeric | Katakana | ExtendNumLet, ExtendNumLet) => true,
+                    (ExtendNumLet, ALetter | HebrewLetter | Numeric | Katakana) => true,
+                    (RegionalIndicator, RegionalIndicator) => regional_count % 2 == 1,
+                    _ => false,
+                }
+            };
+
+            if !join {
+                bounds.push(chars[i].0);
+            }
+        }
+
+        if !right.is_ignorable() {
+            far_left = solid_left;
+            solid_left = Some(right);
+            regional_count = if right == RegionalIndicator { regional_count + 1 } else { 0 };
+        }
+    }
+
+    if !s.is_empty() {
+        bounds.push(s.len());
+    }
+
+    bounds
+}
+
+fn split_at_bounds<'a>(s: &'a str, bounds: &[usize]) -> Vec<&'a str> {
+    bounds.windows(2).map(|w| &s[w[0]..w[1]]).collect()
+}
+
+/// Return the extended grapheme clusters of the string.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::graphemes;
+///
+/// let answer = graphemes("e\u{301}\r\n🇺🇦👨‍👩‍👧");
+/// assert_eq!(answer, vec!["e\u{301}", "\r\n", "🇺🇦", "👨‍👩‍👧"]);
+/// ```
+pub fn graphemes(s: &str) -> Vec<&str> {
+    split_at_bounds(s, &grapheme_bounds(s))
+}
+
+/// Return the string split at every word boundary, keeping the spaces and
+/// punctuation between words as separate pieces.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::split_word_bounds;
+///
+/// let answer = split_word_bounds("can't stop, 3.14!");
+/// assert_eq!(answer, vec!["can't", " ", "stop", ",", " ", "3.14", "!"]);
+/// ```
+pub fn split_word_bounds(s: &str) -> Vec<&str> {
+    split_at_bounds(s, &word_bounds(s))
+}
+
+/// Return the words of the string, i.e. the pieces between word boundaries
+/// that contain a letter or a digit.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::words;
+///
+/// let answer = words("Привет, мир! It's 2024.");
+/// assert_eq!(answer, vec!["Привет", "мир", "It's", "2024"]);
+/// ```
+pub fn words(s: &str) -> Vec<&str> {
+    split_word_bounds(s)
+        .into_iter()
+        .filter(|w| w.chars().any(char::is_alphanumeric))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graphemes() {
+        let answer = graphemes("");
+        assert_eq!(answer, Vec::<&str>::new());
+
+        let answer = graphemes("한국어 ");
+        assert_eq!(answer, vec!["한", "국", "어", " "]);
+
+        let answer = graphemes("\u{1112}\u{1161}\u{11AB}a");
+        assert_eq!(answer, vec!["\u{1112}\u{1161}\u{11AB}", "a"]);
+
+        let answer = graphemes("🇺🇦🇵🇱🇩");
+        assert_eq!(answer, vec!["🇺🇦", "🇵🇱", "🇩"]);
+
+        let answer = graphemes("👍🏽!");
+        assert_eq!(answer, vec!["👍🏽", "!"]);
+
+        let answer = graphemes("नमस्ते");
+        assert_eq!(answer, vec!["न", "म", "स्ते"]);
+
+        let answer = graphemes("क्\u{200D}ष क्");
+        assert_eq!(answer, vec!["क्\u{200D}ष", " ", "क्"]);
+
+        let answer = graphemes("ক্\u{09BC}ষি");
+        assert_eq!(answer, vec!["ক্\u{09BC}ষি"]);
+    }
+
+    #[test]
+    fn test_split_word_bounds() {
+        let answer = split_word_bounds("foo_bar  baz");
+        assert_eq!(answer, vec!["foo_bar", "  ", "baz"]);
+
+        let answer = split_word_bounds("1,000.5 x2");
+        assert_eq!(answer, vec!["1,000.5", " ", "x2"]);
+
+        let answer = split_word_bounds("a.b. c:");
+        assert_eq!(answer, vec!["a.b", ".", " ", "c", ":"]);
+
+        let answer = split_word_bounds("東京タワー");
+        assert_eq!(answer, vec!["東", "京", "タワー"]);
+
+        let answer = split_word_bounds("🇺🇦\u{200D}🇵🇱🇩a");
+        assert_eq!(answer, vec!["🇺🇦\u{200D}", "🇵🇱", "🇩", "a"]);
+
+        let flags = "🇺🇦".repeat(20000);
+        assert_eq!(split_word_bounds(&flags).len(), 20000);
+    }
+
+    #[test]
+    fn test_words() {
+        let answer = words("Hello world!");
+        assert_eq!(answer, vec!["Hello", "world"]);
+
+        let answer = words("  -- ");
+        assert_eq!(answer, Vec::<&str>::new());
+
+        let answer = words("naïve cafe\u{301}");
+        assert_eq!(answer, vec!["naïve", "cafe\u{301}"]);
+    }
+
+    #[test]
+    fn test_text_unit_count() {
+        let answer = TextUnit::Chars.count("🇺🇦");
+        assert_eq!(answer, 2);
+
+        let answer = TextUnit::Graphemes.count("🇺🇦");
+        assert_eq!(answer, 1);
+
+        let answer = TextUnit::Graphemes.count("");
+        assert_eq!(answer, 0);
+    }
+}
@@ -0,0 +1,127 @@
+use std::hash::Hash;
+use std::ops::Range;
+
+use crate::collections::{shortest_window_containing, FrequencyTable};
+
+/// Return the covering windows that contain no smaller covering window,
+/// ordered by position.
+fn covering_ranges<T: Hash + Eq>(items: &[T], required: &[T]) -> Vec<Range<usize>> {
+    if required.is_empty() {
+        return std::iter::once(0..0).collect();
+    }
+
+    let mut table = FrequencyTable::new(required);
+    let mut result: Vec<Range<usize>> = vec![];
+    let mut start = 0;
+
+    for (end, item) in items.iter().enumerate() {
+        table.push(item);
+
+        if !table.is_covered() {
+            continue;
+        }
+
+        while table.is_spare(&items[start]) {
+            table.pop(&items[start]);
+            start += 1;
+        }
+
+        // With the same start as the previous window this one only adds items on the right.
+        if result.last().is_none_or(|last| last.start < start) {
+            result.push(start..end + 1);
+        }
+    }
+
+    result
+}
+
+/// Convert char ranges of the text into substrings.
+fn char_ranges_to_str(text: &str, ranges: Vec<Range<usize>>) -> Vec<&str> {
+    let mut offsets: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+    offsets.push(text.len());
+
+    ranges.into_iter().map(|r| &text[offsets[r.start]..offsets[r.end]]).collect()
+}
+
+/// Return the leftmost shortest substring of the text that contains every
+/// char of `required`, as many times as it's repeated there. See
+/// [`shortest_window_containing`] for other items than chars.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::min_window;
+///
+/// let answer = min_window("ADOBECODEBANC", "ABC");
+/// assert_eq!(answer, Some("BANC"));
+///
+/// let answer = min_window("мама мыла раму", "ммм");
+/// assert_eq!(answer, Some("мама м"));
+///
+/// let answer = min_window("a", "aa");
+/// assert_eq!(answer, None);
+/// ```
+pub fn min_window<'a>(text: &'a str, required: &str) -> Option<&'a str> {
+    let best = shortest_window_containing(text.chars(), required.chars())?;
+
+    char_ranges_to_str(text, vec![best]).pop()
+}
+
+/// Return every minimal covering substring of the text, i.e. every substring
+/// that contains the required chars with their multiplicities while no
+/// shorter substring inside it does. They are ordered by position.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::covering_windows;
+///
+/// let answer = covering_windows("abcbca", "abc");
+/// assert_eq!(answer, vec!["abc", "bca"]);
+/// ```
+pub fn covering_windows<'a>(text: &'a str, required: &str) -> Vec<&'a str> {
+    let chars: Vec<char> = text.chars().collect();
+    let required: Vec<char> = required.chars().collect();
+
+    char_ranges_to_str(text, covering_ranges(&chars, &required))
+}
+
+/// Return the bounds of every minimal covering window of the slice, ordered
+/// by position.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::covering_windows_slice;
+///
+/// let answer = covering_windows_slice(&["x", "y", "z", "x", "y"], &["x", "y"]);
+/// assert_eq!(answer, vec![0..2, 1..4, 3..5]);
+/// ```
+pub fn covering_windows_slice<T: Hash + Eq>(items: &[T], required: &[T]) -> Vec<Range<usize>> {
+    covering_ranges(items, required)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_window() {
+        assert_eq!(min_window("", ""), Some(""));
+        assert_eq!(min_window("abc", ""), Some(""));
+        assert_eq!(min_window("", "a"), None);
+        assert_eq!(min_window("aa", "aa"), Some("aa"));
+        assert_eq!(min_window("cabwefgewcwaefgcf", "cae"), Some("cwae"));
+        assert_eq!(min_window("日本語の本", "本の"), Some("の本"));
+    }
+
+    #[test]
+    fn test_covering_windows() {
+        assert_eq!(covering_windows("aXbXaab", "aab"), vec!["aXbXa", "bXaa", "aab"]);
+        assert_eq!(covering_windows("bbb", "a"), Vec::<&str>::new());
+        assert_eq!(covering_windows("ааа", "а"), vec!["а", "а", "а"]);
+
+        let answer = covering_windows_slice(&[1, 2, 1, 1, 2], &[1, 2]);
+        assert_eq!(answer, vec![0..2, 1..3, 3..5]);
+    }
+}
@@ -0,0 +1,246 @@
+use super::graphemes;
+use super::tokenizer::{in_table, is_regional_indicator, is_zero_width, Table};
+
+/// East Asian Wide and Fullwidth ranges, including emoji presentation.
+const WIDE: Table = &[
+    (0x1100, 0x115F), (0x231A, 0x231B), (0x2329, 0x232A), (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0), (0x23F3, 0x23F3), (0x25FD, 0x25FE), (0x2614, 0x2615),
+    (0x2648, 0x2653), (0x267F, 0x267F), (0x2693, 0x2693), (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB), (0x26BD, 0x26BE), (0x26C4, 0x26C5), (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4), (0x26EA, 0x26EA), (0x26F2, 0x26F3), (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA), (0x26FD, 0x26FD), (0x2705, 0x2705), (0x270A, 0x270B),
+    (0x2728, 0x2728), (0x274C, 0x274C), (0x274E, 0x274E), (0x2753, 0x2755),
+    (0x2757, 0x2757), (0x2795, 0x2797), (0x27B0, 0x27B0), (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C), (0x2B50, 0x2B50), (0x2B55, 0x2B55), (0x2E80, 0x303E),
+    (0x3041, 0x33FF), (0x3400, 0x4DBF), (0x4E00, 0x9FFF), (0xA000, 0xA4CF),
+    (0xA960, 0xA97F), (0xAC00, 0xD7A3), (0xF900, 0xFAFF), (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F), (0xFF00, 0xFF60), (0xFFE0, 0xFFE6), (0x16FE0, 0x16FE4),
+    (0x17000, 0x187F7), (0x1B000, 0x1B2FF), (0x1F004, 0x1F004), (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E), (0x1F191, 0x1F19A), (0x1F200, 0x1F202), (0x1F210, 0x1F23B),
+    (0x1F240, 0x1F248), (0x1F250, 0x1F251), (0x1F260, 0x1F265), (0x1F300, 0x1F320),
+    (0x1F32D, 0x1F335), (0x1F337, 0x1F37C), (0x1F37E, 0x1F393), (0x1F3A0, 0x1F3CA),
+    (0x1F3CF, 0x1F3D3), (0x1F3E0, 0x1F3F0), (0x1F3F4, 0x1F3F4), (0x1F3F8, 0x1F43E),
+    (0x1F440, 0x1F440), (0x1F442, 0x1F4FC), (0x1F4FF, 0x1F53D), (0x1F54B, 0x1F54E),
+    (0x1F550, 0x1F567), (0x1F57A, 0x1F57A), (0x1F595, 0x1F596), (0x1F5A4, 0x1F5A4),
+    (0x1F5FB, 0x1F64F), (0x1F680, 0x1F6C5), (0x1F6CC, 0x1F6CC), (0x1F6D0, 0x1F6D2),
+    (0x1F6D5, 0x1F6D7), (0x1F6EB, 0x1F6EC), (0x1F6F4, 0x1F6FC), (0x1F7E0, 0x1F7EB),
+    (0x1F90C, 0x1F93A), (0x1F93C, 0x1F945), (0x1F947, 0x1F9FF), (0x1FA70, 0x1FAFF),
+    (0x20000, 0x2FFFD), (0x30000, 0x3FFFD),
+];
+
+fn char_width(c: char) -> usize {
+    if is_zero_width(c) {
+        0
+    } else if in_table(WIDE, c) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Return the columns of a grapheme cluster: those of its widest char, or
+/// two for a flag or a char followed by the emoji presentation selector.
+fn grapheme_width(grapheme: &str) -> usize {
+    let is_emoji = grapheme.ends_with('\u{FE0F}') || grapheme.chars().filter(|&c| is_regional_indicator(c)).count() == 2;
+
+    if is_emoji {
+        2
+    } else {
+        grapheme.chars().map(char_width).max().unwrap_or(0)
+    }
+}
+
+/// Return the number of terminal columns the text takes, grapheme cluster
+/// by grapheme cluster: CJK, other wide chars and emoji sequences count as
+/// two, combining marks and control chars as zero.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::display_width;
+///
+/// assert_eq!(display_width("hello"), 5);
+/// assert_eq!(display_width("日本語"), 6);
+/// assert_eq!(display_width("e\u{301}te\u{301}"), 3);
+/// assert_eq!(display_width("👨‍👩‍👧"), 2);
+/// ```
+pub fn display_width(s: &str) -> usize {
+    graphemes(s).into_iter().map(grapheme_width).sum()
+}
+
+/// Return whether true or false if the text may break at the char: any
+/// whitespace except the no-break spaces.
+fn is_break(c: char) -> bool {
+    c.is_whitespace() && !matches!(c, '\u{00A0}' | '\u{2007}' | '\u{202F}')
+}
+
+/// Wrap every paragraph of the text into lines of words, greedily.
+/// Words wider than the width are broken between graphemes.
+fn wrap_paragraphs(text: &str, width: usize) -> Vec<Vec<String>> {
+    let mut paragraphs = vec![];
+
+    for paragraph in text.split('\n') {
+        let mut lines = vec![];
+        let mut line = String::new();
+        let mut line_width = 0;
+
+        for word in paragraph.split(is_break).filter(|word| !word.is_empty()) {
+            let word_width = display_width(word);
+
+            if !line.is_empty() && line_width + 1 + word_width <= width {
+                line.push(' ');
+                line.push_str(word);
+                line_width += 1 + word_width;
+                continue;
+            }
+
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+
+            line_width = 0;
+
+            for grapheme in graphemes(word) {
+                let grapheme_width = grapheme_width(grapheme);
+
+                if !line.is_empty() && line_width + grapheme_width > width {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0;
+                }
+
+                line.push_str(grapheme);
+                line_width += grapheme_width;
+            }
+        }
+
+        lines.push(line);
+        paragraphs.push(lines);
+    }
+
+    paragraphs
+}
+
+/// Wrap the text into lines at most `width` columns wide, breaking at
+/// whitespace other than no-break spaces. Runs of whitespace collapse into
+/// one space, newlines start a new paragraph and words wider than the width
+/// are broken.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::wrap;
+///
+/// let answer = wrap("The quick brown fox jumps over the lazy dog", 10);
+/// assert_eq!(answer, vec!["The quick", "brown fox", "jumps over", "the lazy", "dog"]);
+///
+/// let answer = wrap("日本語のテキスト", 6);
+/// assert_eq!(answer, vec!["日本語", "のテキ", "スト"]);
+/// ```
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    wrap_paragraphs(text, width).into_iter().flatten().collect()
+}
+
+/// Wrap the text like [`wrap`] and join the lines with newlines.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::fill;
+///
+/// let answer = fill("Usage: gutils [OPTIONS] <COMMAND>", 16);
+/// assert_eq!(answer, "Usage: gutils\n[OPTIONS]\n<COMMAND>".to_string());
+/// ```
+pub fn fill(text: &str, width: usize) -> String {
+    wrap(text, width).join("\n")
+}
+
+/// Spread the line's words over the width, giving the leftmost gaps the
+/// extra spaces when they can't be spread evenly.
+fn justify_line(line: &str, width: usize) -> String {
+    let words: Vec<&str> = line.split(' ').collect();
+    let gaps = words.len() - 1;
+
+    if gaps == 0 {
+        return line.to_string();
+    }
+
+    let spaces = width.saturating_sub(words.iter().map(|w| display_width(w)).sum());
+    let (base, extra) = (spaces / gaps, spaces % gaps);
+    let mut result = words[0].to_string();
+
+    for (i, word) in words[1..].iter().enumerate() {
+        let gap = base + usize::from(i < extra);
+        result.extend(std::iter::repeat_n(' ', gap.max(1)));
+        result.push_str(word);
+    }
+
+    result
+}
+
+/// Wrap the text like [`wrap`] and justify every line to exactly `width`
+/// columns by spreading spaces between words. The last line of every
+/// paragraph and lines with a single word stay left-aligned.
+///
+/// # Examples
+///
+/// ```
+/// use gutils::string::justify;
+///
+/// let answer = justify("This is an example of text justification.", 16);
+/// assert_eq!(answer, "This    is    an\nexample  of text\njustification.".to_string());
+/// ```
+pub fn justify(text: &str, width: usize) -> String {
+    let mut lines = vec![];
+
+    for paragraph in wrap_paragraphs(text, width) {
+        let last = paragraph.len() - 1;
+
+        for (i, line) in paragraph.into_iter().enumerate() {
+            lines.push(if i < last { justify_line(&line, width) } else { line });
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_width() {
+        assert_eq!(display_width(""), 0);
+        assert_eq!(display_width("Привет"), 6);
+        assert_eq!(display_width("한국어"), 6);
+        assert_eq!(display_width("ｆｕｌｌ"), 8);
+        assert_eq!(display_width("🚀 go"), 5);
+        assert_eq!(display_width("a\tb\n"), 2);
+        assert_eq!(display_width("🇺🇦🇵🇱"), 4);
+        assert_eq!(display_width("❤\u{FE0F}!"), 3);
+        assert_eq!(display_width("\u{1112}\u{1161}\u{11AB}"), 2);
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("", 10), vec![""]);
+        assert_eq!(wrap("a\n\nb  c", 10), vec!["a", "", "b c"]);
+        assert_eq!(wrap("abcdefgh ij", 3), vec!["abc", "def", "gh", "ij"]);
+        assert_eq!(wrap("a b", 0), vec!["a", "b"]);
+        assert_eq!(wrap("cafe\u{301} crème brûlée", 11), vec!["cafe\u{301} crème", "brûlée"]);
+        assert_eq!(wrap("中文 text", 5), vec!["中文", "text"]);
+        assert_eq!(wrap("a 10\u{00A0}km b", 6), vec!["a", "10\u{00A0}km", "b"]);
+        assert_eq!(wrap("👨‍👩‍👧 👨‍👩‍👧 x", 5), vec!["👨‍👩‍👧 👨‍👩‍👧", "x"]);
+    }
+
+    #[test]
+    fn test_justify() {
+        let answer = justify("What must be acknowledgment shall be", 16);
+        assert_eq!(answer, "What   must   be\nacknowledgment\nshall be".to_string());
+
+        let answer = justify("один два три\nчетыре", 11);
+        assert_eq!(answer, "один    два\nтри\nчетыре".to_string());
+
+        let answer = justify("日本 語 テキ スト", 9);
+        assert_eq!(answer, "日本   語\nテキ スト".to_string());
+    }
+}